@@ -0,0 +1,297 @@
+//! Recording a simulation's grid each step, and saving the result as a
+//! numbered PPM sequence or an animated GIF.
+
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use ndarray::Array2;
+
+use crate::{write_ppm, Color};
+
+/// Captures frames of palette indices, built up like
+/// `Recorder::new(vec![Color::Black, Color::White]).delay(10)`.
+#[derive(Clone, Debug)]
+pub struct Recorder {
+    palette: Vec<Color>,
+    /// Hundredths of a second between frames
+    delay: u16,
+    frames: Vec<Array2<u8>>,
+}
+
+impl Recorder {
+    /// Record with up to 256 colors, which frames refer to by index.
+    pub fn new(palette: Vec<Color>) -> Self {
+        assert!(
+            (1..=256).contains(&palette.len()),
+            "palette must have 1 to 256 colors"
+        );
+        Recorder {
+            palette,
+            delay: 10,
+            frames: vec![],
+        }
+    }
+
+    /// Show each frame for `hundredths` of a second
+    pub fn delay(mut self, hundredths: u16) -> Self {
+        self.delay = hundredths;
+        self
+    }
+
+    /// Capture a frame, mapping each cell to an index into the palette.
+    pub fn capture<T>(&mut self, array: &Array2<T>, index: impl Fn(&T) -> u8) {
+        if let Some(first) = self.frames.first() {
+            assert_eq!(first.dim(), array.dim(), "frame size changed");
+        }
+        let frame = array.map(|e| {
+            let i = index(e);
+            assert!((i as usize) < self.palette.len(), "no palette color {}", i);
+            i
+        });
+        self.frames.push(frame);
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Write each frame to `dir/frame_NNNN.ppm`, creating `dir` if needed.
+    pub fn write_ppm_sequence(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        let digits = self.frames.len().saturating_sub(1).to_string().len().max(4);
+        for (n, frame) in self.frames.iter().enumerate() {
+            let path = dir.join(format!("frame_{:0w$}.ppm", n, w = digits));
+            let w = BufWriter::new(File::create(path)?);
+            write_ppm(w, frame, |&i| self.palette[i as usize])?;
+        }
+        Ok(())
+    }
+
+    /// Write every frame as a looping GIF animation.
+    pub fn write_gif(&self, mut w: impl Write) -> io::Result<()> {
+        let (height, width) = self.frames.first().map_or((0, 0), |f| f.dim());
+        let (width, height) = match (u16::try_from(width), u16::try_from(height)) {
+            (Ok(w), Ok(h)) => (w, h),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "frames too big for a GIF",
+                ))
+            }
+        };
+        // bits per palette index, at least 1
+        let bits = (usize::BITS - (self.palette.len() - 1).leading_zeros()).max(1);
+
+        w.write_all(b"GIF89a")?;
+        w.write_all(&width.to_le_bytes())?;
+        w.write_all(&height.to_le_bytes())?;
+        // global color table, 8-bit color resolution, no background or aspect
+        w.write_all(&[0xf0 | (bits - 1) as u8, 0, 0])?;
+        for i in 0..1 << bits {
+            let (r, g, b) = self.palette.get(i).map_or((0, 0, 0), |c| c.to_rgb());
+            w.write_all(&[r, g, b])?;
+        }
+        // loop forever
+        w.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\0\0\0")?;
+
+        let min_code_size = bits.max(2) as u8;
+        for frame in &self.frames {
+            // graphic control extension: no disposal or transparency
+            w.write_all(&[0x21, 0xf9, 4, 0])?;
+            w.write_all(&self.delay.to_le_bytes())?;
+            w.write_all(&[0, 0])?;
+            // image descriptor: the whole screen, no local color table
+            w.write_all(&[0x2c, 0, 0, 0, 0])?;
+            w.write_all(&width.to_le_bytes())?;
+            w.write_all(&height.to_le_bytes())?;
+            w.write_all(&[0])?;
+
+            w.write_all(&[min_code_size])?;
+            let pixels: Vec<u8> = frame.iter().copied().collect();
+            for block in lzw_encode(min_code_size, &pixels).chunks(255) {
+                w.write_all(&[block.len() as u8])?;
+                w.write_all(block)?;
+            }
+            w.write_all(&[0])?;
+        }
+        w.write_all(b";")?;
+        w.flush()
+    }
+
+    /// Save as a GIF if `path` ends in `.gif`, or else as a PPM sequence in
+    /// the directory `path`.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if path.extension().is_some_and(|e| e == "gif") {
+            self.write_gif(BufWriter::new(File::create(path)?))
+        } else {
+            self.write_ppm_sequence(path)
+        }
+    }
+}
+
+/// Packs variable-width codes least significant bit first.
+#[derive(Default)]
+struct BitWriter {
+    out: Vec<u8>,
+    acc: u32,
+    nbits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u32) {
+        self.acc |= (code as u32) << self.nbits;
+        self.nbits += width;
+        while self.nbits >= 8 {
+            self.out.push(self.acc as u8);
+            self.acc >>= 8;
+            self.nbits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.nbits > 0 {
+            self.out.push(self.acc as u8);
+        }
+        self.out
+    }
+}
+
+/// GIF's flavor of LZW, with codes of up to 12 bits.
+fn lzw_encode(min_code_size: u8, data: &[u8]) -> Vec<u8> {
+    const MAX_CODES: u16 = 1 << 12;
+    let clear: u16 = 1 << min_code_size;
+    let end = clear + 1;
+    let reset_width = min_code_size as u32 + 1;
+
+    let mut dict: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut width = reset_width;
+    let mut bits = BitWriter::default();
+    bits.write(clear, width);
+
+    let mut data = data.iter().copied();
+    let mut current = match data.next() {
+        Some(byte) => byte as u16,
+        None => {
+            bits.write(end, width);
+            return bits.finish();
+        }
+    };
+    for byte in data {
+        if let Some(&code) = dict.get(&(current, byte)) {
+            current = code;
+            continue;
+        }
+        bits.write(current, width);
+        if next < MAX_CODES {
+            dict.insert((current, byte), next);
+            next += 1;
+            // the decoder learns codes a step behind us, so it widens a
+            // step later
+            if next > 1 << width && width < 12 {
+                width += 1;
+            }
+        } else {
+            bits.write(clear, width);
+            dict.clear();
+            next = end + 1;
+            width = reset_width;
+        }
+        current = byte as u16;
+    }
+    bits.write(current, width);
+    bits.write(end, width);
+    bits.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A by-the-book GIF LZW decoder to check the encoder against
+    fn lzw_decode(min_code_size: u8, data: &[u8]) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let mut table: Vec<Vec<u8>> = vec![];
+        let mut width = 0;
+        let mut prev: Option<usize> = None;
+        let mut out = vec![];
+        let (mut acc, mut nbits, mut bytes) = (0u32, 0, data.iter());
+        loop {
+            while nbits < width.max(min_code_size as u32 + 1) {
+                acc |= (*bytes.next().expect("ran out of data") as u32) << nbits;
+                nbits += 8;
+            }
+            let width_now = width.max(min_code_size as u32 + 1);
+            let code = (acc & ((1 << width_now) - 1)) as usize;
+            acc >>= width_now;
+            nbits -= width_now;
+            if code == clear {
+                table = (0..clear).map(|i| vec![i as u8]).collect();
+                table.extend([vec![], vec![]]);
+                width = min_code_size as u32 + 1;
+                prev = None;
+                continue;
+            }
+            if code == end {
+                return out;
+            }
+            let entry = match (table.get(code), prev) {
+                (Some(e), _) => e.clone(),
+                (None, Some(p)) => {
+                    let mut e = table[p].clone();
+                    e.push(table[p][0]);
+                    e
+                }
+                (None, None) => panic!("bad first code {}", code),
+            };
+            if let Some(p) = prev {
+                if table.len() < 4096 {
+                    let mut e = table[p].clone();
+                    e.push(entry[0]);
+                    table.push(e);
+                    if table.len() == 1 << width && width < 12 {
+                        width += 1;
+                    }
+                }
+            }
+            out.extend(&entry);
+            prev = Some(code);
+        }
+    }
+
+    #[test]
+    fn test_lzw_round_trip() {
+        let mut x = 12345u32;
+        let noise: Vec<u8> = (0..20000)
+            .map(|_| {
+                x = x.wrapping_mul(1103515245).wrapping_add(12345);
+                (x >> 16) as u8 & 3
+            })
+            .collect();
+        for data in [vec![], vec![1], vec![0; 5000], noise] {
+            assert_eq!(lzw_decode(2, &lzw_encode(2, &data)), data);
+        }
+        let bytes: Vec<u8> = (0..=255).cycle().take(10000).collect();
+        assert_eq!(lzw_decode(8, &lzw_encode(8, &bytes)), bytes);
+    }
+
+    #[test]
+    fn test_gif() {
+        let mut rec = Recorder::new(vec![Color::Black, Color::White]).delay(5);
+        rec.capture(&Array2::from_elem((2, 3), false), |&x| x as u8);
+        rec.capture(&Array2::from_elem((2, 3), true), |&x| x as u8);
+        let mut gif = vec![];
+        rec.write_gif(&mut gif).unwrap();
+        assert!(gif.starts_with(b"GIF89a\x03\0\x02\0\xf0\0\0\0\0\0\xe5\xe5\xe5"));
+        assert!(gif.ends_with(b"\0;"));
+    }
+}
@@ -0,0 +1,248 @@
+//! Small fixed-size sets of indices, packed into bits.
+
+use std::{
+    error::Error,
+    fmt,
+    iter::FromIterator,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Sub, SubAssign},
+};
+
+/// A set of indices in `0..BITS`, stored in `WORDS` 64-bit words.
+///
+/// `WORDS` can't be worked out from `BITS` on stable Rust, so sets of more
+/// than 64 bits need it spelled out: `BitSet<100, 2>`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitSet<const BITS: usize, const WORDS: usize = 1> {
+    words: [u64; WORDS],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseBitSetError {
+    /// Byte offset of the bad character
+    pub pos: usize,
+    pub c: char,
+}
+
+impl fmt::Display for ParseBitSetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unexpected {:?} at {}", self.c, self.pos)
+    }
+}
+
+impl Error for ParseBitSetError {}
+
+impl<const BITS: usize, const WORDS: usize> Default for BitSet<BITS, WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const BITS: usize, const WORDS: usize> BitSet<BITS, WORDS> {
+    const FITS: () = assert!(BITS <= WORDS * 64, "not enough words for BITS");
+
+    pub const fn new() -> Self {
+        let () = Self::FITS;
+        BitSet { words: [0; WORDS] }
+    }
+
+    /// Every index in `0..BITS`
+    pub fn full() -> Self {
+        !Self::new()
+    }
+
+    fn check(i: usize) {
+        assert!(i < BITS, "index {} out of range for {} bits", i, BITS);
+    }
+
+    pub fn insert(&mut self, i: usize) {
+        Self::check(i);
+        self.words[i / 64] |= 1 << (i % 64);
+    }
+
+    pub fn remove(&mut self, i: usize) {
+        Self::check(i);
+        self.words[i / 64] &= !(1 << (i % 64));
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        i < BITS && self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    /// Number of indices in the set
+    pub fn len(&self) -> u32 {
+        self.words.iter().map(|w| w.count_ones()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Number of indices in both sets
+    pub fn common_with(&self, other: &Self) -> u32 {
+        (*self & *other).len()
+    }
+
+    /// The indices in the set, lowest first
+    pub fn iter(&self) -> impl Iterator<Item = usize> {
+        let words = self.words;
+        (0..WORDS).flat_map(move |n| {
+            let mut w = words[n];
+            std::iter::from_fn(move || {
+                if w == 0 {
+                    return None;
+                }
+                let bit = w.trailing_zeros() as usize;
+                // clear the lowest set bit
+                w &= w - 1;
+                Some(n * 64 + bit)
+            })
+        })
+    }
+
+    /// Parse letters as indices, `a` being 0, like `"acf"`
+    pub fn from_letters(s: &str) -> Result<Self, ParseBitSetError> {
+        let mut out = Self::new();
+        for (pos, c) in s.char_indices() {
+            match (c as usize).wrapping_sub('a' as usize) {
+                i if c.is_ascii_lowercase() && i < BITS => out.insert(i),
+                _ => return Err(ParseBitSetError { pos, c }),
+            }
+        }
+        Ok(out)
+    }
+
+    /// Parse a binary number, like `"10110"`, so the last digit is index 0
+    pub fn from_binary(s: &str) -> Result<Self, ParseBitSetError> {
+        let mut out = Self::new();
+        let len = s.chars().count();
+        for (n, (pos, c)) in s.char_indices().enumerate() {
+            let i = len - 1 - n;
+            match c {
+                '0' if i < BITS => (),
+                '1' if i < BITS => out.insert(i),
+                _ => return Err(ParseBitSetError { pos, c }),
+            }
+        }
+        Ok(out)
+    }
+
+    pub fn words(&self) -> &[u64; WORDS] {
+        &self.words
+    }
+
+    /// Clear any bits past `BITS` that word-wide operations set
+    fn masked(mut self) -> Self {
+        for (n, w) in self.words.iter_mut().enumerate() {
+            let valid = BITS.saturating_sub(n * 64);
+            if valid < 64 {
+                *w &= (1 << valid) - 1;
+            }
+        }
+        self
+    }
+}
+
+/// The low bits of `word`, as a set
+impl<const BITS: usize, const WORDS: usize> From<u64> for BitSet<BITS, WORDS> {
+    fn from(word: u64) -> Self {
+        let mut out = Self::new();
+        if WORDS > 0 {
+            out.words[0] = word;
+        }
+        out.masked()
+    }
+}
+
+impl<const BITS: usize, const WORDS: usize> FromIterator<usize> for BitSet<BITS, WORDS> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut out = Self::new();
+        for i in iter {
+            out.insert(i);
+        }
+        out
+    }
+}
+
+macro_rules! impl_set_op {
+    ($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident, |$a:ident, $b:ident| $e:expr) => {
+        impl<const BITS: usize, const WORDS: usize> $Op for BitSet<BITS, WORDS> {
+            type Output = Self;
+
+            fn $op(mut self, rhs: Self) -> Self {
+                self.$op_assign(rhs);
+                self
+            }
+        }
+
+        impl<const BITS: usize, const WORDS: usize> $OpAssign for BitSet<BITS, WORDS> {
+            fn $op_assign(&mut self, rhs: Self) {
+                for ($a, $b) in self.words.iter_mut().zip(rhs.words) {
+                    *$a = $e;
+                }
+            }
+        }
+    };
+}
+
+impl_set_op!(BitOr, bitor, BitOrAssign, bitor_assign, |a, b| *a | b);
+impl_set_op!(BitAnd, bitand, BitAndAssign, bitand_assign, |a, b| *a & b);
+impl_set_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, |a, b| *a ^ b);
+impl_set_op!(Sub, sub, SubAssign, sub_assign, |a, b| *a & !b);
+
+/// Every index in `0..BITS` not in the set
+impl<const BITS: usize, const WORDS: usize> Not for BitSet<BITS, WORDS> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        BitSet {
+            words: self.words.map(|w| !w),
+        }
+        .masked()
+    }
+}
+
+/// Letters for sets that fit in the alphabet, like `acf`, otherwise indices.
+impl<const BITS: usize, const WORDS: usize> fmt::Debug for BitSet<BITS, WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if BITS <= 26 {
+            for i in self.iter() {
+                fmt::Write::write_char(f, (b'a' + i as u8) as char)?;
+            }
+            Ok(())
+        } else {
+            f.debug_set().entries(self.iter()).finish()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitset() {
+        let a = BitSet::<7>::from_letters("cfbeg").unwrap();
+        let b = BitSet::<7>::from_letters("gcbe").unwrap();
+        assert_eq!(format!("{:?}", a), "bcefg");
+        assert_eq!(a.len(), 5);
+        assert_eq!(a.common_with(&b), 4);
+        assert_eq!(format!("{:?}", a - b), "f");
+        assert_eq!(format!("{:?}", !a), "ad");
+        assert_eq!(BitSet::<7>::full().len(), 7);
+        assert_eq!(
+            BitSet::<7>::from_letters("abh"),
+            Err(ParseBitSetError { pos: 2, c: 'h' })
+        );
+
+        let n = BitSet::<5>::from_binary("10110").unwrap();
+        assert_eq!(n, BitSet::from(0b10110));
+        assert_eq!(n.iter().collect::<Vec<_>>(), [1, 2, 4]);
+        assert!(BitSet::<4>::from_binary("10110").is_err());
+
+        let mut big: BitSet<100, 2> = [3, 64, 99].into_iter().collect();
+        assert_eq!(format!("{:?}", big), "{3, 64, 99}");
+        big.remove(64);
+        assert!(!big.contains(64) && big.contains(99));
+        assert_eq!((!big).len(), 98);
+    }
+}
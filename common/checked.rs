@@ -0,0 +1,252 @@
+//! Arithmetic for puzzle answers that can be overflow-checked in release.
+//!
+//! With the `checked` feature, every operation on an [`Answer`] is checked,
+//! and an overflow panics with the location of the offending expression.
+//! Without it, an [`Answer`] is just its inner integer.
+
+use std::{
+    fmt::{self, Debug, Display},
+    iter::Sum,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign},
+};
+
+use num_traits::{
+    CheckedAdd, CheckedDiv, CheckedMul, CheckedNeg, CheckedRem, CheckedSub, One, Zero,
+};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Answer<T>(pub T);
+
+/// Do `a op b`, checking for overflow if the `checked` feature is on.
+#[track_caller]
+fn apply<T: Display + Copy>(
+    a: T,
+    b: T,
+    checked: fn(&T, &T) -> Option<T>,
+    unchecked: fn(T, T) -> T,
+    op: &str,
+) -> T {
+    if !cfg!(feature = "checked") {
+        return unchecked(a, b);
+    }
+    match checked(&a, &b) {
+        Some(x) => x,
+        None => panic!("overflow computing {} {} {}", a, op, b),
+    }
+}
+
+macro_rules! impl_op {
+    ($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident, $Checked:ident, $checked:ident, $sym:literal) => {
+        impl<T: $Op<Output = T> + $Checked + Display + Copy> $Op for Answer<T> {
+            type Output = Answer<T>;
+
+            #[track_caller]
+            fn $op(self, rhs: Answer<T>) -> Answer<T> {
+                Answer(apply(self.0, rhs.0, T::$checked, T::$op, $sym))
+            }
+        }
+
+        impl<T: $Op<Output = T> + $Checked + Display + Copy> $Op<T> for Answer<T> {
+            type Output = Answer<T>;
+
+            #[track_caller]
+            fn $op(self, rhs: T) -> Answer<T> {
+                Answer(apply(self.0, rhs, T::$checked, T::$op, $sym))
+            }
+        }
+
+        impl<T: $Op<Output = T> + $Checked + Display + Copy> $OpAssign for Answer<T> {
+            #[track_caller]
+            fn $op_assign(&mut self, rhs: Answer<T>) {
+                self.0 = apply(self.0, rhs.0, T::$checked, T::$op, $sym);
+            }
+        }
+
+        impl<T: $Op<Output = T> + $Checked + Display + Copy> $OpAssign<T> for Answer<T> {
+            #[track_caller]
+            fn $op_assign(&mut self, rhs: T) {
+                self.0 = apply(self.0, rhs, T::$checked, T::$op, $sym);
+            }
+        }
+    };
+}
+
+impl_op!(
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    CheckedAdd,
+    checked_add,
+    "+"
+);
+impl_op!(
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    CheckedSub,
+    checked_sub,
+    "-"
+);
+impl_op!(
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    CheckedMul,
+    checked_mul,
+    "*"
+);
+impl_op!(
+    Div,
+    div,
+    DivAssign,
+    div_assign,
+    CheckedDiv,
+    checked_div,
+    "/"
+);
+impl_op!(
+    Rem,
+    rem,
+    RemAssign,
+    rem_assign,
+    CheckedRem,
+    checked_rem,
+    "%"
+);
+
+impl<T: Neg<Output = T> + CheckedNeg + Display + Copy> Neg for Answer<T> {
+    type Output = Answer<T>;
+
+    #[track_caller]
+    fn neg(self) -> Answer<T> {
+        if !cfg!(feature = "checked") {
+            return Answer(-self.0);
+        }
+        match self.0.checked_neg() {
+            Some(x) => Answer(x),
+            None => panic!("overflow computing -{}", self.0),
+        }
+    }
+}
+
+impl<T: Add<Output = T> + CheckedAdd + Zero + Display + Copy> Answer<T> {
+    /// Add up `iter`, reporting an overflow at the caller.
+    ///
+    /// `#[track_caller]` doesn't reach through `Iterator::sum`, so an
+    /// overflow in `.sum()` is reported somewhere in `core` instead.
+    #[track_caller]
+    pub fn sum_checked(iter: impl IntoIterator<Item = Answer<T>>) -> Answer<T> {
+        let mut total = T::zero();
+        for x in iter {
+            total = apply(total, x.0, T::checked_add, T::add, "+");
+        }
+        Answer(total)
+    }
+}
+
+/// Prefer [`Answer::sum_checked`], which can say where an overflow happened.
+impl<T: Add<Output = T> + CheckedAdd + Zero + Display + Copy> Sum for Answer<T> {
+    fn sum<I: Iterator<Item = Answer<T>>>(iter: I) -> Answer<T> {
+        Answer::sum_checked(iter)
+    }
+}
+
+impl<T: Add<Output = T> + CheckedAdd + Zero + Display + Copy> Zero for Answer<T> {
+    fn zero() -> Answer<T> {
+        Answer(T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl<T: Mul<Output = T> + CheckedMul + One + Display + Copy> One for Answer<T> {
+    fn one() -> Answer<T> {
+        Answer(T::one())
+    }
+}
+
+impl<T> From<T> for Answer<T> {
+    fn from(x: T) -> Answer<T> {
+        Answer(x)
+    }
+}
+
+impl<T: Debug> Debug for Answer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<T: Display> Display for Answer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(all(test, feature = "checked"))]
+mod tests {
+    use super::*;
+    use std::{
+        cell::RefCell,
+        panic::{self, AssertUnwindSafe},
+        sync::Once,
+    };
+
+    thread_local! {
+        static PANIC: RefCell<Option<(String, String, u32)>> = const { RefCell::new(None) };
+    }
+
+    /// The message, file and line of the panic in `f`
+    fn panic_in(f: impl FnOnce()) -> (String, String, u32) {
+        static HOOK: Once = Once::new();
+        HOOK.call_once(|| {
+            let default = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                let location = info.location().expect("panics have locations");
+                let message = info.payload_as_str().unwrap_or_default().to_string();
+                PANIC.with(|p| {
+                    *p.borrow_mut() = Some((message, location.file().to_string(), location.line()))
+                });
+                default(info);
+            }));
+        });
+        assert!(panic::catch_unwind(AssertUnwindSafe(f)).is_err());
+        PANIC
+            .with(|p| p.borrow_mut().take())
+            .expect("panic was recorded")
+    }
+
+    /// Check that `$e` overflows with `$message`, reported on this line.
+    macro_rules! assert_overflow_here {
+        ($e:expr, $message:literal) => {
+            let caught = panic_in(|| {
+                let _ = $e;
+            });
+            assert_eq!(caught, ($message.to_string(), file!().to_string(), line!()));
+        };
+    }
+
+    #[test]
+    fn test_overflow_location() {
+        let max = Answer(i32::MAX);
+        let min = Answer(i32::MIN);
+        assert_overflow_here!(max + 1, "overflow computing 2147483647 + 1");
+        assert_overflow_here!(min - Answer(1), "overflow computing -2147483648 - 1");
+        assert_overflow_here!(max * 2, "overflow computing 2147483647 * 2");
+        assert_overflow_here!(min / -1, "overflow computing -2147483648 / -1");
+        let items = [max, Answer(1)];
+        let sum = Answer::sum_checked;
+        assert_overflow_here!(sum(items), "overflow computing 2147483647 + 1");
+
+        // plain `.sum()` still catches it, just not where
+        let (message, _, _) = panic_in(|| {
+            let _ = items.into_iter().sum::<Answer<i32>>();
+        });
+        assert_eq!(message, "overflow computing 2147483647 + 1");
+    }
+}
@@ -0,0 +1,146 @@
+//! Iterator adapters yielding fixed-size arrays, in the spirit of
+//! [`ArrayCollect`](crate::ArrayCollect).
+
+use std::iter::Fuse;
+
+use crate::ArrayCollect;
+
+pub trait ArrayIterExt: Iterator + Sized {
+    /// Split into consecutive `[T; N]` chunks. Items left over at the end
+    /// are kept in [`Arrays::remainder`].
+    fn arrays<const N: usize>(self) -> Arrays<Self, N> {
+        assert!(N > 0, "chunks must not be empty");
+        Arrays {
+            iter: self.fuse(),
+            remainder: None,
+        }
+    }
+
+    /// Every run of `N` consecutive items, overlapping, like
+    /// [`slice::windows`].
+    fn array_windows<const N: usize>(self) -> ArrayWindows<Self, N>
+    where
+        Self::Item: Clone,
+    {
+        assert!(N > 0, "windows must not be empty");
+        ArrayWindows {
+            iter: self,
+            window: None,
+        }
+    }
+}
+
+impl<I: Iterator> ArrayIterExt for I {}
+
+/// See [`ArrayIterExt::arrays`].
+pub struct Arrays<I: Iterator, const N: usize> {
+    iter: Fuse<I>,
+    remainder: Option<[Option<I::Item>; N]>,
+}
+
+impl<I: Iterator, const N: usize> Arrays<I, N> {
+    /// The items after the last full chunk, once iteration has ended
+    pub fn remainder(&self) -> impl Iterator<Item = &I::Item> {
+        self.remainder.iter().flatten().flatten()
+    }
+
+    pub fn into_remainder(self) -> impl Iterator<Item = I::Item> {
+        self.remainder.into_iter().flatten().flatten()
+    }
+}
+
+impl<I: Iterator, const N: usize> Iterator for Arrays<I, N> {
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<[I::Item; N]> {
+        if self.remainder.is_some() {
+            return None;
+        }
+        let items: [Option<I::Item>; N] = [(); N].map(|_| self.iter.next());
+        // the iterator is fused, so only the last slot can tell if it ran out
+        if items[N - 1].is_none() {
+            self.remainder = Some(items);
+            return None;
+        }
+        Some(items.map(|x| x.expect("iterator is fused")))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.remainder.is_some() {
+            return (0, Some(0));
+        }
+        let (lo, hi) = self.iter.size_hint();
+        (lo / N, hi.map(|hi| hi / N))
+    }
+}
+
+/// See [`ArrayIterExt::array_windows`].
+pub struct ArrayWindows<I: Iterator, const N: usize> {
+    iter: I,
+    window: Option<[I::Item; N]>,
+}
+
+impl<I, const N: usize> Iterator for ArrayWindows<I, N>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<[I::Item; N]> {
+        match &mut self.window {
+            None => {
+                let window: [I::Item; N] = self.iter.by_ref().take(N).try_array_collect().ok()?;
+                self.window = Some(window.clone());
+                Some(window)
+            }
+            Some(window) => {
+                let next = self.iter.next()?;
+                window.rotate_left(1);
+                window[N - 1] = next;
+                Some(window.clone())
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.iter.size_hint();
+        match self.window {
+            Some(_) => (lo, hi),
+            None => (
+                lo.saturating_sub(N - 1),
+                hi.map(|hi| hi.saturating_sub(N - 1)),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arrays() {
+        let mut chunks = (1..=7).arrays::<3>();
+        assert_eq!(chunks.next(), Some([1, 2, 3]));
+        assert_eq!(chunks.next(), Some([4, 5, 6]));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.remainder().collect::<Vec<_>>(), [&7]);
+        assert_eq!((1..=6).arrays::<2>().count(), 3);
+        assert_eq!((1..=6).arrays::<3>().into_remainder().count(), 0);
+    }
+
+    #[test]
+    fn test_windows() {
+        let windows: Vec<[i32; 3]> = (1..=5).array_windows().collect();
+        assert_eq!(windows, [[1, 2, 3], [2, 3, 4], [3, 4, 5]]);
+        assert_eq!((1..=5).array_windows::<3>().size_hint(), (3, Some(3)));
+        assert_eq!((1..=2).array_windows::<3>().next(), None);
+        assert_eq!(
+            (0u64..).array_windows::<2>().size_hint(),
+            (usize::MAX - 1, None)
+        );
+        assert_eq!((0u64..).array_windows::<2>().nth(2), Some([2, 3]));
+    }
+}
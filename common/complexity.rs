@@ -0,0 +1,189 @@
+//! Guessing the time complexity of a solution by timing it on generated
+//! inputs of growing size.
+
+use std::{
+    env, fmt,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// Keep re-running a part at a given size until this much time has passed,
+/// so tiny inputs don't time as pure noise.
+const MIN_SAMPLE_TIME: Duration = Duration::from_millis(20);
+
+/// Scales to try when `AOC_COMPLEXITY` doesn't say otherwise.
+pub const DEFAULT_SIZES: [usize; 5] = [250, 500, 1000, 2000, 4000];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Complexity {
+    Constant,
+    Log,
+    Linear,
+    NLogN,
+    Quadratic,
+    Cubic,
+}
+
+impl Complexity {
+    pub const ALL: [Complexity; 6] = [
+        Complexity::Constant,
+        Complexity::Log,
+        Complexity::Linear,
+        Complexity::NLogN,
+        Complexity::Quadratic,
+        Complexity::Cubic,
+    ];
+
+    /// The (unscaled) cost of an input of size `n`
+    pub fn cost(self, n: f64) -> f64 {
+        // keep log n positive so log-space fitting works for n = 1
+        let log = (n + 1.).log2();
+        match self {
+            Complexity::Constant => 1.,
+            Complexity::Log => log,
+            Complexity::Linear => n,
+            Complexity::NLogN => n * log,
+            Complexity::Quadratic => n * n,
+            Complexity::Cubic => n * n * n,
+        }
+    }
+}
+
+impl fmt::Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Complexity::Constant => "O(1)",
+            Complexity::Log => "O(log n)",
+            Complexity::Linear => "O(n)",
+            Complexity::NLogN => "O(n log n)",
+            Complexity::Quadratic => "O(n²)",
+            Complexity::Cubic => "O(n³)",
+        })
+    }
+}
+
+/// Timings at each input size and how well each complexity class fits them.
+#[derive(Clone, Debug)]
+pub struct Estimate {
+    pub samples: Vec<(usize, Duration)>,
+    /// Every class with its fitting error, best fit first
+    pub fits: Vec<(Complexity, f64)>,
+}
+
+impl Estimate {
+    pub fn best(&self) -> Complexity {
+        self.fits[0].0
+    }
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, time) in &self.samples {
+            writeln!(f, "  n = {:>8}: {:?}", n, time)?;
+        }
+        write!(f, "  best fit: {}", self.best())?;
+        if let Some((runner_up, error)) = self.fits.get(1) {
+            write!(
+                f,
+                " (error {:.3}, next best {} with {:.3})",
+                self.fits[0].1, runner_up, error
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Fit each complexity class to `(size, time)` samples.
+///
+/// Fitting is done in log space, `log t = log c + log f(n)`, so that the
+/// biggest inputs don't drown out the rest. The error is the RMS of the
+/// residuals.
+pub fn fit(samples: &[(usize, Duration)]) -> Vec<(Complexity, f64)> {
+    assert!(!samples.is_empty(), "no samples to fit");
+    let mut fits: Vec<(Complexity, f64)> = Complexity::ALL
+        .into_iter()
+        .map(|class| {
+            let residuals: Vec<f64> = samples
+                .iter()
+                .map(|&(n, t)| t.as_secs_f64().max(1e-12).ln() - class.cost(n as f64).ln())
+                .collect();
+            let scale = residuals.iter().sum::<f64>() / residuals.len() as f64;
+            let error =
+                residuals.iter().map(|r| (r - scale).powi(2)).sum::<f64>() / residuals.len() as f64;
+            (class, error.sqrt())
+        })
+        .collect();
+    fits.sort_by(|a, b| a.1.total_cmp(&b.1));
+    fits
+}
+
+/// Time `run` on `generate(n)` for each of `sizes` and fit the results.
+pub fn estimate<T, R>(
+    sizes: &[usize],
+    mut generate: impl FnMut(usize) -> T,
+    mut run: impl FnMut(&T) -> R,
+) -> Estimate {
+    let samples = sizes
+        .iter()
+        .map(|&n| {
+            let input = generate(n);
+            let mut runs = 0u32;
+            let start = Instant::now();
+            while runs == 0 || start.elapsed() < MIN_SAMPLE_TIME {
+                black_box(run(black_box(&input)));
+                runs += 1;
+            }
+            (n, start.elapsed() / runs)
+        })
+        .collect::<Vec<_>>();
+    let fits = fit(&samples);
+    Estimate { samples, fits }
+}
+
+/// A day's `part1` or `part2`
+pub type Part<T, R> = fn(&T) -> R;
+
+/// If `AOC_COMPLEXITY` is set, estimate the complexity of each part and
+/// print it to stderr.
+///
+/// `AOC_COMPLEXITY` can be a comma-separated list of sizes to try instead of
+/// the defaults.
+pub fn estimate_if_requested<T, R>(
+    mut generate: impl FnMut(usize) -> T,
+    parts: &[(&str, Part<T, R>)],
+) {
+    let sizes = match env::var("AOC_COMPLEXITY") {
+        Err(_) => return,
+        Ok(s) if s.trim().is_empty() || s == "1" => DEFAULT_SIZES.to_vec(),
+        Ok(s) => s
+            .split(',')
+            .map(|n| n.trim().parse().expect("invalid AOC_COMPLEXITY size"))
+            .collect(),
+    };
+    for (name, part) in parts {
+        eprintln!("{}:\n{}", name, estimate(&sizes, &mut generate, part));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(f: impl Fn(f64) -> f64) -> Vec<(usize, Duration)> {
+        [100, 200, 400, 800, 1600]
+            .into_iter()
+            .map(|n| (n, Duration::from_secs_f64(f(n as f64))))
+            .collect()
+    }
+
+    #[test]
+    fn test_fit() {
+        assert_eq!(fit(&samples(|n| 3e-9 * n))[0].0, Complexity::Linear);
+        assert_eq!(fit(&samples(|n| 1e-9 * n * n))[0].0, Complexity::Quadratic);
+        assert_eq!(
+            fit(&samples(|n| 5e-8 * n * n.log2() + 1e-6))[0].0,
+            Complexity::NLogN
+        );
+        assert_eq!(fit(&samples(|_| 1e-3))[0].0, Complexity::Constant);
+    }
+}
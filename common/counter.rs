@@ -0,0 +1,181 @@
+//! A multiset for counting how often things appear.
+
+use std::{
+    cmp::Reverse,
+    collections::{btree_map, BTreeMap},
+    iter::FromIterator,
+    ops::{Add, AddAssign, Sub, SubAssign},
+};
+
+/// How many times each key has been seen, iterated in key order.
+///
+/// Keys with a count of zero are never stored.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Counter<K: Ord> {
+    counts: BTreeMap<K, usize>,
+}
+
+impl<K: Ord> Default for Counter<K> {
+    fn default() -> Self {
+        Counter {
+            counts: BTreeMap::new(),
+        }
+    }
+}
+
+impl<K: Ord> Counter<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, key: K) {
+        self.add_n(key, 1);
+    }
+
+    pub fn add_n(&mut self, key: K, n: usize) {
+        if n > 0 {
+            *self.counts.entry(key).or_default() += n;
+        }
+    }
+
+    /// Take away up to `n` of `key`, returning how many were taken
+    pub fn remove_n(&mut self, key: &K, n: usize) -> usize {
+        match self.counts.get_mut(key) {
+            Some(count) if *count > n => {
+                *count -= n;
+                n
+            }
+            Some(_) => self.counts.remove(key).unwrap_or(0),
+            None => 0,
+        }
+    }
+
+    pub fn get(&self, key: &K) -> usize {
+        self.counts.get(key).copied().unwrap_or(0)
+    }
+
+    /// Number of distinct keys
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Sum of every count
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// The key seen most often, the smallest one if tied
+    pub fn most_common(&self) -> Option<(&K, usize)> {
+        self.iter()
+            .reduce(|best, x| if x.1 > best.1 { x } else { best })
+    }
+
+    /// The key seen least often, the smallest one if tied
+    pub fn least_common(&self) -> Option<(&K, usize)> {
+        self.iter()
+            .reduce(|best, x| if x.1 < best.1 { x } else { best })
+    }
+
+    /// Every key and its count, most common first
+    pub fn by_count(&self) -> Vec<(&K, usize)> {
+        let mut v: Vec<_> = self.iter().collect();
+        v.sort_by_key(|&(_, n)| Reverse(n));
+        v
+    }
+
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> {
+        self.counts.keys()
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, usize)> {
+        self.counts.iter().map(|(k, &n)| (k, n))
+    }
+}
+
+impl<K: Ord> FromIterator<K> for Counter<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<K: Ord> Extend<K> for Counter<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.add(key);
+        }
+    }
+}
+
+impl<K: Ord> IntoIterator for Counter<K> {
+    type Item = (K, usize);
+    type IntoIter = btree_map::IntoIter<K, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+impl<K: Ord> AddAssign for Counter<K> {
+    fn add_assign(&mut self, rhs: Counter<K>) {
+        for (key, n) in rhs {
+            self.add_n(key, n);
+        }
+    }
+}
+
+impl<K: Ord> Add for Counter<K> {
+    type Output = Counter<K>;
+
+    fn add(mut self, rhs: Counter<K>) -> Counter<K> {
+        self += rhs;
+        self
+    }
+}
+
+/// Counts can't go below zero; anything taken away past that is ignored.
+impl<K: Ord> SubAssign for Counter<K> {
+    fn sub_assign(&mut self, rhs: Counter<K>) {
+        for (key, n) in rhs {
+            self.remove_n(&key, n);
+        }
+    }
+}
+
+impl<K: Ord> Sub for Counter<K> {
+    type Output = Counter<K>;
+
+    fn sub(mut self, rhs: Counter<K>) -> Counter<K> {
+        self -= rhs;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter() {
+        let c: Counter<char> = "NNCBCHB".chars().collect();
+        assert_eq!(c.get(&'N'), 2);
+        assert_eq!(c.get(&'Z'), 0);
+        assert_eq!(c.len(), 4);
+        assert_eq!(c.total(), 7);
+        assert_eq!(c.most_common(), Some((&'B', 2)));
+        assert_eq!(c.least_common(), Some((&'H', 1)));
+        assert_eq!(c.keys().collect::<String>(), "BCHN");
+
+        let more: Counter<char> = "BBZ".chars().collect();
+        let sum = c.clone() + more.clone();
+        assert_eq!(sum.by_count()[0], (&'B', 4));
+        assert_eq!(sum.get(&'Z'), 1);
+        let diff = more - c;
+        assert_eq!(diff.iter().collect::<Vec<_>>(), [(&'Z', 1)]);
+    }
+}
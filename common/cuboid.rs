@@ -0,0 +1,154 @@
+//! Axis-aligned boxes in `N` dimensions, and unions of them.
+
+use std::fmt;
+
+/// An axis-aligned box covering `min[d]..=max[d]` along each axis `d`
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid<const N: usize> {
+    pub min: [i64; N],
+    pub max: [i64; N],
+}
+
+impl<const N: usize> Cuboid<N> {
+    /// The box with corners `min` and `max`, or `None` if it'd be empty
+    pub fn new(min: [i64; N], max: [i64; N]) -> Option<Self> {
+        (0..N)
+            .all(|d| min[d] <= max[d])
+            .then_some(Cuboid { min, max })
+    }
+
+    /// The number of integer points inside.
+    ///
+    /// Panics if it doesn't fit in an `i128`, which takes more than two
+    /// dimensions of huge sides.
+    pub fn volume(&self) -> i128 {
+        (0..N).fold(1i128, |volume, d| {
+            let side = self.max[d] as i128 - self.min[d] as i128 + 1;
+            volume
+                .checked_mul(side)
+                .expect("cuboid volume overflows i128")
+        })
+    }
+
+    pub fn contains(&self, point: [i64; N]) -> bool {
+        (0..N).all(|d| self.min[d] <= point[d] && point[d] <= self.max[d])
+    }
+
+    pub fn intersection(&self, other: &Cuboid<N>) -> Option<Cuboid<N>> {
+        let mut min = self.min;
+        let mut max = self.max;
+        for d in 0..N {
+            min[d] = min[d].max(other.min[d]);
+            max[d] = max[d].min(other.max[d]);
+        }
+        Cuboid::new(min, max)
+    }
+
+    /// `self` with `other` cut out, as at most `2N` disjoint boxes
+    pub fn subtract(&self, other: &Cuboid<N>) -> Vec<Cuboid<N>> {
+        let Some(overlap) = self.intersection(other) else {
+            return vec![*self];
+        };
+        let mut pieces = vec![];
+        // slice off the parts sticking out along each axis in turn, shrinking
+        // what's left down to the overlap
+        let mut rest = *self;
+        for d in 0..N {
+            if rest.min[d] < overlap.min[d] {
+                let mut below = rest;
+                below.max[d] = overlap.min[d] - 1;
+                pieces.push(below);
+            }
+            if rest.max[d] > overlap.max[d] {
+                let mut above = rest;
+                above.min[d] = overlap.max[d] + 1;
+                pieces.push(above);
+            }
+            rest.min[d] = overlap.min[d];
+            rest.max[d] = overlap.max[d];
+        }
+        pieces
+    }
+}
+
+impl<const N: usize> fmt::Debug for Cuboid<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sides: Vec<String> = (0..N)
+            .map(|d| format!("{}..={}", self.min[d], self.max[d]))
+            .collect();
+        write!(f, "Cuboid[{}]", sides.join(", "))
+    }
+}
+
+/// A union of boxes, kept as disjoint pieces
+#[derive(Clone, Debug, Default)]
+pub struct BoxSet<const N: usize> {
+    boxes: Vec<Cuboid<N>>,
+}
+
+impl<const N: usize> BoxSet<N> {
+    pub fn new() -> Self {
+        BoxSet { boxes: vec![] }
+    }
+
+    /// Add every point in `cuboid`
+    pub fn add(&mut self, cuboid: Cuboid<N>) {
+        self.remove(&cuboid);
+        self.boxes.push(cuboid);
+    }
+
+    /// Take away every point in `cuboid`
+    pub fn remove(&mut self, cuboid: &Cuboid<N>) {
+        self.boxes = self.boxes.iter().flat_map(|b| b.subtract(cuboid)).collect();
+    }
+
+    pub fn contains(&self, point: [i64; N]) -> bool {
+        self.boxes.iter().any(|b| b.contains(point))
+    }
+
+    /// The number of integer points covered
+    pub fn volume(&self) -> i128 {
+        self.boxes.iter().map(Cuboid::volume).sum()
+    }
+
+    /// The disjoint boxes making up the set
+    pub fn boxes(&self) -> &[Cuboid<N>] {
+        &self.boxes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube(min: i64, max: i64) -> Cuboid<3> {
+        Cuboid::new([min; 3], [max; 3]).unwrap()
+    }
+
+    #[test]
+    fn test_cuboids() {
+        let a = cube(0, 2);
+        assert_eq!(a.volume(), 27);
+        assert_eq!(a.intersection(&cube(1, 5)), Some(cube(1, 2)));
+        assert_eq!(a.intersection(&cube(3, 5)), None);
+        let pieces = a.subtract(&cube(1, 1));
+        assert_eq!(pieces.len(), 6);
+        assert_eq!(pieces.iter().map(Cuboid::volume).sum::<i128>(), 26);
+        assert_eq!(
+            Cuboid::new([i64::MIN], [i64::MAX]).unwrap().volume(),
+            1 << 64
+        );
+    }
+
+    #[test]
+    fn test_reactor() {
+        let mut set = BoxSet::new();
+        set.add(cube(10, 12));
+        set.add(cube(11, 13));
+        set.remove(&cube(9, 11));
+        set.add(cube(10, 10));
+        assert_eq!(set.volume(), 39);
+        assert!(set.contains([10, 10, 10]));
+        assert!(!set.contains([11, 11, 11]));
+    }
+}
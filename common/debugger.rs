@@ -0,0 +1,163 @@
+//! A tiny step-through REPL for simulation-style days.
+
+use std::{
+    env,
+    io::{self, BufRead, Write},
+};
+
+/// A simulation that can be driven one step at a time by [`debug`].
+pub trait Simulation {
+    /// Advance the simulation by one step.
+    fn step(&mut self);
+
+    /// Draw the current state, e.g. with [`Dots`](crate::Dots).
+    fn render(&self) -> String;
+
+    /// Named counters describing the current state.
+    fn summary(&self) -> Vec<(&'static str, i64)>;
+}
+
+const HELP: &str = "\
+commands:
+  s, step [n]                  step n times (default 1)
+  u, until <counter> <op> <n>  step until a counter compares true (op: == != < <= > >=)
+  p, print                     render the current state
+  i, info                      show the step count and counters
+  h, help                      show this help
+  q, quit                      stop debugging
+an empty line repeats the last command";
+
+/// Give up on `until` after this many steps without the condition holding.
+const UNTIL_LIMIT: usize = 1_000_000;
+
+/// Run the debugger on stdin/stderr if `AOC_DEBUG` is set.
+pub fn debug_if_requested(sim: &mut impl Simulation) {
+    if env::var_os("AOC_DEBUG").is_some() {
+        debug(sim, io::stdin().lock(), io::stderr()).expect("debugger I/O failed");
+    }
+}
+
+/// Interactively step through `sim`, reading commands from `input` until it
+/// ends or `quit` is given.
+pub fn debug<S: Simulation>(
+    sim: &mut S,
+    mut input: impl BufRead,
+    mut out: impl Write,
+) -> io::Result<()> {
+    let mut steps = 0usize;
+    let mut last = String::new();
+    let mut line = String::new();
+    loop {
+        write!(out, "[{}]> ", steps)?;
+        out.flush()?;
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        if line.trim().is_empty() {
+            line.clone_from(&last);
+        } else {
+            last.clone_from(&line);
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => (),
+            ["s" | "step"] => {
+                sim.step();
+                steps += 1;
+            }
+            ["s" | "step", n] => match n.parse::<usize>() {
+                Ok(n) => {
+                    for _ in 0..n {
+                        sim.step();
+                    }
+                    steps += n;
+                }
+                Err(e) => writeln!(out, "bad step count {:?}: {}", n, e)?,
+            },
+            ["u" | "until", name, op, value] => {
+                let value: i64 = match value.parse() {
+                    Ok(v) => v,
+                    Err(e) => {
+                        writeln!(out, "bad value {:?}: {}", value, e)?;
+                        continue;
+                    }
+                };
+                let cmp: fn(&i64, &i64) -> bool = match *op {
+                    "==" => i64::eq,
+                    "!=" => i64::ne,
+                    "<" => i64::lt,
+                    "<=" => i64::le,
+                    ">" => i64::gt,
+                    ">=" => i64::ge,
+                    _ => {
+                        writeln!(out, "unknown comparison {:?}", op)?;
+                        continue;
+                    }
+                };
+                let counter = |sim: &S| {
+                    sim.summary()
+                        .into_iter()
+                        .find(|(n, _)| n == name)
+                        .map(|(_, v)| v)
+                };
+                if counter(sim).is_none() {
+                    writeln!(out, "no counter named {:?}", name)?;
+                    continue;
+                }
+                let mut ran = 0;
+                while !counter(sim).is_some_and(|c| cmp(&c, &value)) {
+                    if ran == UNTIL_LIMIT {
+                        writeln!(out, "gave up after {} steps", ran)?;
+                        break;
+                    }
+                    sim.step();
+                    ran += 1;
+                }
+                steps += ran;
+            }
+            ["p" | "print"] => write!(out, "{}", sim.render())?,
+            ["i" | "info"] => {
+                writeln!(out, "step: {}", steps)?;
+                for (name, value) in sim.summary() {
+                    writeln!(out, "{}: {}", name, value)?;
+                }
+            }
+            ["h" | "help"] => writeln!(out, "{}", HELP)?,
+            ["q" | "quit"] => return Ok(()),
+            _ => writeln!(out, "unknown command {:?} - try `help`", line.trim())?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counter(i64);
+    impl Simulation for Counter {
+        fn step(&mut self) {
+            self.0 += 3;
+        }
+
+        fn render(&self) -> String {
+            format!("{}\n", self.0)
+        }
+
+        fn summary(&self) -> Vec<(&'static str, i64)> {
+            vec![("value", self.0)]
+        }
+    }
+
+    #[test]
+    fn test_commands() {
+        let mut sim = Counter(0);
+        let script = "s\n\ns 3\nu value >= 20\np\ni\nq\ns\n";
+        let mut out = vec![];
+        debug(&mut sim, script.as_bytes(), &mut out).unwrap();
+        assert_eq!(sim.0, 21);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("[7]> 21\n"), "{}", out);
+        assert!(out.contains("step: 7\nvalue: 21\n"), "{}", out);
+    }
+}
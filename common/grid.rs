@@ -0,0 +1,217 @@
+//! A 2D grid of cells, the shape of most map-style puzzles.
+
+use std::{
+    error::Error,
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use ndarray::{Array2, ArrayView1, Axis};
+
+/// A grid of cells indexed by `(row, col)`, wrapping an [`Array2`].
+///
+/// Points are `(x, y)` with `x` the column and `y` the row, signed so that
+/// stepping off the edge is just a failed [`Grid::get_point`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T>(pub Array2<T>);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// A line was a different length than the first line
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The mapping rejected a character
+    BadChar { line: usize, col: usize, c: char },
+}
+
+impl fmt::Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {} has {} cells, expected {}",
+                line + 1,
+                found,
+                expected
+            ),
+            ParseGridError::BadChar { line, col, c } => {
+                write!(f, "unexpected {:?} at line {} col {}", c, line + 1, col + 1)
+            }
+        }
+    }
+}
+
+impl Error for ParseGridError {}
+
+impl<T> Grid<T> {
+    /// Parse a block of text with one cell per character, e.g.
+    /// `Grid::parse(s, |c| c.to_digit(10))`.
+    ///
+    /// Blank lines at the end are ignored.
+    pub fn parse(s: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, ParseGridError> {
+        let mut lines: Vec<&str> = s.lines().collect();
+        while lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
+        let ncols = lines.first().map_or(0, |l| l.chars().count());
+        let mut cells = Vec::with_capacity(lines.len() * ncols);
+        for (line, text) in lines.iter().enumerate() {
+            let before = cells.len();
+            for (col, c) in text.chars().enumerate() {
+                cells.push(f(c).ok_or(ParseGridError::BadChar { line, col, c })?);
+            }
+            let found = cells.len() - before;
+            if found != ncols {
+                return Err(ParseGridError::Ragged {
+                    line,
+                    expected: ncols,
+                    found,
+                });
+            }
+        }
+        Ok(Grid(
+            Array2::from_shape_vec((lines.len(), ncols), cells).expect("shape was checked"),
+        ))
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.0.nrows()
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.0.ncols()
+    }
+
+    /// The cell at `(row, col)`, if it's in bounds
+    pub fn get(&self, (row, col): (usize, usize)) -> Option<&T> {
+        self.0.get((row, col))
+    }
+
+    pub fn get_mut(&mut self, (row, col): (usize, usize)) -> Option<&mut T> {
+        self.0.get_mut((row, col))
+    }
+
+    /// The cell at point `(x, y)`, if it's in bounds
+    pub fn get_point(&self, (x, y): (i64, i64)) -> Option<&T> {
+        self.get((usize::try_from(y).ok()?, usize::try_from(x).ok()?))
+    }
+
+    pub fn get_point_mut(&mut self, (x, y): (i64, i64)) -> Option<&mut T> {
+        self.get_mut((usize::try_from(y).ok()?, usize::try_from(x).ok()?))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = ArrayView1<'_, T>> {
+        self.0.rows().into_iter()
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = ArrayView1<'_, T>> {
+        self.0.columns().into_iter()
+    }
+
+    /// Every cell with its `(row, col)`, row by row
+    pub fn indexed_iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.0.indexed_iter()
+    }
+
+    pub fn into_array(self) -> Array2<T> {
+        self.0
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Flip over the main diagonal, so rows become columns
+    pub fn transpose(&self) -> Grid<T> {
+        Grid(self.0.t().to_owned())
+    }
+
+    /// Rotate a quarter turn clockwise
+    pub fn rotate_cw(&self) -> Grid<T> {
+        let mut t = self.0.t();
+        t.invert_axis(Axis(1));
+        Grid(t.to_owned())
+    }
+
+    /// Rotate a quarter turn counterclockwise
+    pub fn rotate_ccw(&self) -> Grid<T> {
+        let mut t = self.0.t();
+        t.invert_axis(Axis(0));
+        Grid(t.to_owned())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: (usize, usize)) -> &T {
+        &self.0[index]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut T {
+        &mut self.0[index]
+    }
+}
+
+impl<T> From<Array2<T>> for Grid<T> {
+    fn from(a: Array2<T>) -> Grid<T> {
+        Grid(a)
+    }
+}
+
+impl<T> From<Grid<T>> for Array2<T> {
+    fn from(g: Grid<T>) -> Array2<T> {
+        g.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_parse() {
+        let g = Grid::parse("123\n456\n", |c| c.to_digit(10)).unwrap();
+        assert_eq!(g.0, array![[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(g[(1, 0)], 4);
+        assert_eq!(g.get_point((2, 0)), Some(&3));
+        assert_eq!(g.get_point((-1, 0)), None);
+        assert_eq!(g.get((2, 0)), None);
+        // spaces are cells too, even at the end
+        let g = Grid::parse("# \n# \n\n", Some).unwrap();
+        assert_eq!(g.0, array![['#', ' '], ['#', ' ']]);
+        assert_eq!(
+            Grid::parse("12\n3", |c| c.to_digit(10)),
+            Err(ParseGridError::Ragged {
+                line: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Grid::parse("1x", |c| c.to_digit(10)),
+            Err(ParseGridError::BadChar {
+                line: 0,
+                col: 1,
+                c: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn test_rotate() {
+        let g = Grid(array![[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(g.transpose().0, array![[1, 4], [2, 5], [3, 6]]);
+        assert_eq!(g.rotate_cw().0, array![[4, 1], [5, 2], [6, 3]]);
+        assert_eq!(g.rotate_ccw().0, array![[3, 6], [2, 5], [1, 4]]);
+        assert_eq!(g.rotate_cw().rotate_ccw(), g);
+    }
+}
@@ -0,0 +1,208 @@
+//! Saving 2D arrays as images, for maps too big to read as text.
+//!
+//! PPM and PGM are the simplest formats there are; PNG is written with
+//! uncompressed deflate blocks so it needs nothing outside std.
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use ndarray::Array2;
+
+use crate::Color;
+
+impl Color {
+    /// The `(r, g, b)` of this color, using xterm's palette for named colors
+    pub fn to_rgb(self) -> (u8, u8, u8) {
+        match self {
+            Color::Black => (0, 0, 0),
+            Color::Red => (205, 0, 0),
+            Color::Green => (0, 205, 0),
+            Color::Yellow => (205, 205, 0),
+            Color::Blue => (0, 0, 238),
+            Color::Magenta => (205, 0, 205),
+            Color::Cyan => (0, 205, 205),
+            Color::White => (229, 229, 229),
+            Color::Rgb(r, g, b) => (r, g, b),
+        }
+    }
+
+    /// Perceived brightness of this color
+    pub fn to_gray(self) -> u8 {
+        let (r, g, b) = self.to_rgb();
+        ((299 * r as u32 + 587 * g as u32 + 114 * b as u32) / 1000) as u8
+    }
+}
+
+/// Write `array` as a binary PPM, one pixel per cell.
+pub fn write_ppm<T>(
+    mut w: impl Write,
+    array: &Array2<T>,
+    color: impl Fn(&T) -> Color,
+) -> io::Result<()> {
+    write!(w, "P6\n{} {}\n255\n", array.ncols(), array.nrows())?;
+    for e in array {
+        let (r, g, b) = color(e).to_rgb();
+        w.write_all(&[r, g, b])?;
+    }
+    w.flush()
+}
+
+/// Write `array` as a binary PGM, one pixel per cell.
+pub fn write_pgm<T>(
+    mut w: impl Write,
+    array: &Array2<T>,
+    gray: impl Fn(&T) -> u8,
+) -> io::Result<()> {
+    write!(w, "P5\n{} {}\n255\n", array.ncols(), array.nrows())?;
+    for e in array {
+        w.write_all(&[gray(e)])?;
+    }
+    w.flush()
+}
+
+fn crc32(bytes: impl IntoIterator<Item = u8>) -> u32 {
+    !bytes.into_iter().fold(!0u32, |crc, byte| {
+        (0..8).fold(crc ^ byte as u32, |crc, _| {
+            (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg())
+        })
+    })
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (a, b) = bytes.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    b << 16 | a
+}
+
+fn write_chunk(w: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    w.write_all(&(data.len() as u32).to_be_bytes())?;
+    w.write_all(kind)?;
+    w.write_all(data)?;
+    let crc = crc32(kind.iter().chain(data).copied());
+    w.write_all(&crc.to_be_bytes())
+}
+
+/// Wrap `data` in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = u16::MAX as usize;
+    let mut out = Vec::with_capacity(data.len() + data.len() / MAX_BLOCK * 5 + 11);
+    // deflate, 32K window, no dictionary, fastest
+    out.extend([0x78, 0x01]);
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+/// Write `array` as an RGB PNG, one pixel per cell.
+pub fn write_png<T>(
+    mut w: impl Write,
+    array: &Array2<T>,
+    color: impl Fn(&T) -> Color,
+) -> io::Result<()> {
+    let (height, width) = array.dim();
+    w.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+    let mut header = Vec::with_capacity(13);
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // 8-bit RGB, deflate, standard filtering, no interlacing
+    header.extend([8, 2, 0, 0, 0]);
+    write_chunk(&mut w, b"IHDR", &header)?;
+
+    let mut pixels = Vec::with_capacity(height * (width * 3 + 1));
+    for row in array.rows() {
+        // no filter
+        pixels.push(0);
+        for e in row {
+            let (r, g, b) = color(e).to_rgb();
+            pixels.extend([r, g, b]);
+        }
+    }
+    write_chunk(&mut w, b"IDAT", &zlib_stored(&pixels))?;
+    write_chunk(&mut w, b"IEND", &[])?;
+    w.flush()
+}
+
+/// Save `array` as an image, in a format picked by the extension of `path`:
+/// `.png`, `.ppm` or `.pgm`.
+pub fn save_image<T>(
+    path: &Path,
+    array: &Array2<T>,
+    color: impl Fn(&T) -> Color,
+) -> io::Result<()> {
+    // only touch the file once we know we can write it
+    let create = || File::create(path).map(BufWriter::new);
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("png") => write_png(create()?, array, color),
+        Some("ppm") => write_ppm(create()?, array, color),
+        Some("pgm") => write_pgm(create()?, array, |e| color(e).to_gray()),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("unknown image format for {}", path.display()),
+        )),
+    }
+}
+
+/// If `AOC_IMAGE` is set to a path, save `array` there with [`save_image`].
+pub fn save_image_if_requested<T>(array: &Array2<T>, color: impl Fn(&T) -> Color) {
+    if let Some(path) = env::var_os("AOC_IMAGE") {
+        let path = Path::new(&path);
+        save_image(path, array, color).expect("could not save image");
+        eprintln!("saved image to {}", path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(*b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(*b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn test_formats() {
+        let a = array![[0u8, 1], [1, 0]];
+        let color = |&x: &u8| if x == 1 { Color::White } else { Color::Black };
+
+        let mut ppm = vec![];
+        write_ppm(&mut ppm, &a, color).unwrap();
+        assert_eq!(
+            ppm,
+            b"P6\n2 2\n255\n\0\0\0\xe5\xe5\xe5\xe5\xe5\xe5\0\0\0".to_vec()
+        );
+
+        let mut png = vec![];
+        write_png(&mut png, &a, color).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x02\0\0\0\x02"));
+        assert!(png.ends_with(b"\0\0\0\0IEND\xae\x42\x60\x82"));
+    }
+    #[test]
+    fn test_save_unknown_format() {
+        let path = env::temp_dir().join(format!("aoc-image-{}.jpg", std::process::id()));
+        let err = save_image(&path, &array![[Color::Red]], |&c| c).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(!path.exists());
+    }
+}
@@ -1,24 +1,73 @@
-#![warn(unsafe_op_in_unsafe_fn)]
+#![deny(unsafe_code)]
 
 use std::{
+    env,
+    error::Error,
     fmt::{self, Debug, Display, Write},
     fs::File,
-    io::{self, Read},
-    mem::MaybeUninit,
-    panic::{self, AssertUnwindSafe},
-    path::Path,
-    ptr,
+    io::{self, BufRead, BufReader, IsTerminal, Read, StdinLock},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
 };
 
 use ndarray::Array2;
 
+mod animation;
+mod bitset;
+mod checked;
+mod chunks;
+mod complexity;
+mod counter;
+mod cuboid;
+mod debugger;
+mod grid;
+mod image;
+mod math;
+mod matrix;
+mod neighbors;
+mod ocr;
+mod parse;
+mod point;
+mod range_set;
+mod regions;
+mod render;
+mod rotation;
+mod search;
+
+pub use animation::Recorder;
+pub use bitset::{BitSet, ParseBitSetError};
+pub use checked::Answer;
+pub use chunks::{ArrayIterExt, ArrayWindows, Arrays};
+pub use complexity::{
+    estimate, estimate_if_requested, fit, Complexity, Estimate, Part, DEFAULT_SIZES,
+};
+pub use counter::Counter;
+pub use cuboid::{BoxSet, Cuboid};
+pub use debugger::{debug, debug_if_requested, Simulation};
+pub use grid::{Grid, ParseGridError};
+pub use image::{save_image, save_image_if_requested, write_pgm, write_png, write_ppm};
+pub use math::{crt, extended_gcd, gcd, lcm, mod_inverse, mod_pow, ModInt};
+pub use matrix::Matrix;
+pub use neighbors::{neighbors, Connectivity, Edges};
+pub use ocr::{ocr, ocr_str, OcrError};
+pub use parse::{ints, ints_array, match_pattern, ParseLineError};
+pub use point::{ParsePointError, Point2, Point3};
+pub use range_set::RangeSet;
+pub use regions::{flood_fill, label_components, Component, Labels};
+pub use render::{Color, Renderer};
+pub use rotation::Rotation;
+pub use search::{astar, bfs, dijkstra, Route};
+
 #[macro_export]
 macro_rules! get_input {
+    (@path) => {
+        $crate::input_path(module_path!())
+    };
     () => {
-        $crate::read_input(std::path::Path::new(&format!(
-            "inputs/{}.txt",
-            module_path!()
-        )))
+        $crate::read_input(&$crate::get_input!(@path))
     };
     (lines) => {
         $crate::get_input!().lines()
@@ -26,32 +75,100 @@ macro_rules! get_input {
     (parsed) => {
         $crate::get_input!(lines).map(|line| line.parse().expect("invalid parse"))
     };
+    (stream) => {
+        $crate::stream_input(&$crate::get_input!(@path))
+    };
+    (stream parsed) => {
+        $crate::get_input!(stream).map(|line| line.parse().expect("invalid parse"))
+    };
 }
 
-/// shrlorp an input path or read from stdin if it doesn't exist
-pub fn read_input(path: &Path) -> String {
+/// The input path for a day: the first command-line argument if given,
+/// otherwise `inputs/<day>.txt`.
+pub fn input_path(day: &str) -> PathBuf {
+    env::args_os()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| format!("inputs/{}.txt", day).into())
+}
+
+/// Where an input comes from
+enum Source {
+    File(File),
+    /// stdin, with a prompt to show before reading it, if any
+    Stdin(Option<String>),
+}
+
+/// Find an input path, or stdin if it's `-`.
+///
+/// If the path doesn't exist and stdin is a terminal, prompt for the input
+/// there instead. If stdin isn't a terminal, nobody's around to type it, so
+/// fail rather than hang.
+fn find_input(path: &Path) -> Source {
+    if path == Path::new("-") {
+        return Source::Stdin(None);
+    }
     match File::open(path) {
-        Ok(mut f) => {
-            let mut s = String::new();
-            f.read_to_string(&mut s).expect("could not read file");
-            s
-        }
+        Ok(f) => Source::File(f),
         Err(e) if e.kind() == io::ErrorKind::NotFound => {
-            println!(
+            if !io::stdin().is_terminal() {
+                panic!(
+                    "input file {} not found and stdin is not a terminal - pass `-` to read piped input",
+                    path.display()
+                );
+            }
+            Source::Stdin(Some(format!(
                 "input file {} not found - enter your input and press Ctrl-D when done",
                 path.display()
-            );
-            let mut s = String::new();
-            io::stdin()
-                .lock()
-                .read_to_string(&mut s)
-                .expect("could not read stdin");
-            s
+            )))
         }
         Err(e) => panic!("Unknown error reading input: {:?}", e),
     }
 }
 
+/// Take stdin for reading. Reading drains it, so this only works once.
+fn take_stdin(prompt: Option<String>) -> StdinLock<'static> {
+    static TAKEN: AtomicBool = AtomicBool::new(false);
+    if TAKEN.swap(true, Ordering::Relaxed) {
+        panic!("stdin can only be streamed once - solve every part in a single pass");
+    }
+    if let Some(prompt) = prompt {
+        eprintln!("{}", prompt);
+    }
+    io::stdin().lock()
+}
+
+fn read_all(mut reader: impl Read) -> String {
+    let mut s = String::new();
+    reader.read_to_string(&mut s).expect("could not read input");
+    s
+}
+
+/// shrlorp an input path or read from stdin if it doesn't exist
+///
+/// Stdin is kept after the first read, so every part can ask for it.
+pub fn read_input(path: &Path) -> String {
+    static STDIN: OnceLock<String> = OnceLock::new();
+    match find_input(path) {
+        Source::File(f) => read_all(f),
+        Source::Stdin(prompt) => STDIN.get_or_init(|| read_all(take_stdin(prompt))).clone(),
+    }
+}
+
+/// Lazily read an input line by line, for inputs too big to shrlorp.
+///
+/// Like [`read_input`], this falls back to stdin if the path doesn't exist.
+/// Stdin isn't kept, so it can only be streamed once.
+pub fn stream_input(path: &Path) -> impl Iterator<Item = String> {
+    let reader: Box<dyn BufRead> = match find_input(path) {
+        Source::File(f) => Box::new(BufReader::new(f)),
+        Source::Stdin(prompt) => Box::new(take_stdin(prompt)),
+    };
+    reader
+        .lines()
+        .map(|line| line.expect("could not read input line"))
+}
+
 pub struct PanicOnError;
 
 impl Debug for PanicOnError {
@@ -86,49 +203,97 @@ where
     }
 }
 
-pub trait ArrayCollect<T> {
-    fn array_collect<const N: usize>(self) -> Option<[T; N]>;
+/// Why an iterator didn't have exactly the number of items asked for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArrayCollectError {
+    pub expected: usize,
+    /// How many items there were, up to `expected`
+    pub found: usize,
+    /// Whether there were more than `expected` items
+    pub leftovers: bool,
 }
 
-// adapted from stdlib
-unsafe fn array_assume_init<T, const N: usize>(array: [MaybeUninit<T>; N]) -> [T; N] {
-    // SAFETY:
-    // * The caller guarantees that all elements of the array are initialized
-    // * `MaybeUninit<T>` and T are guaranteed to have the same layout
-    // * `MaybeUninit` does not drop, so there are no double-frees
-    // And thus the conversion is safe
-    unsafe {
-        // intrinsics::assert_inhabited::<[T; N]>();
-        (&array as *const _ as *const [T; N]).read()
+impl Display for ArrayCollectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.leftovers {
+            write!(f, "expected {} items, found more", self.expected)
+        } else {
+            write!(f, "expected {} items, found {}", self.expected, self.found)
+        }
+    }
+}
+
+impl Error for ArrayCollectError {}
+
+pub trait ArrayCollect<T>: Sized {
+    /// Collect exactly `N` items into an array.
+    fn try_array_collect<const N: usize>(self) -> Result<[T; N], ArrayCollectError>;
+
+    /// Collect exactly `N` items into an array, or `None` if there are more
+    /// or fewer.
+    fn array_collect<const N: usize>(self) -> Option<[T; N]> {
+        self.try_array_collect().ok()
     }
 }
 
 impl<I: Iterator> ArrayCollect<I::Item> for I {
-    fn array_collect<const N: usize>(mut self) -> Option<[I::Item; N]> {
-        // yes i promise this is a sound way to do this
-        // https://doc.rust-lang.org/nomicon/unchecked-uninit.html
-        let mut array: [MaybeUninit<I::Item>; N] = unsafe { MaybeUninit::uninit().assume_init() };
-        let mut written_indexes: usize = 0;
-        match panic::catch_unwind(AssertUnwindSafe(|| {
-            for dst in &mut array {
-                dst.write(self.next()?);
-                written_indexes = written_indexes.wrapping_add(1);
-            }
-            Some(())
-        })) {
-            Ok(x) => x?,
-            Err(e) => {
-                // clean up after ourselves
-                for i in 0..written_indexes {
-                    unsafe {
-                        ptr::drop_in_place(array[i].as_mut_ptr());
-                    }
-                }
-                panic::resume_unwind(e)
-            }
+    fn try_array_collect<const N: usize>(self) -> Result<[I::Item; N], ArrayCollectError> {
+        let mut iter = self.fuse();
+        // If we come up short, dropping this drops everything collected so far.
+        let items: [Option<I::Item>; N] = [(); N].map(|_| iter.next());
+        let found = items.iter().take_while(|x| x.is_some()).count();
+        let leftovers = found == N && iter.next().is_some();
+        if found < N || leftovers {
+            return Err(ArrayCollectError {
+                expected: N,
+                found,
+                leftovers,
+            });
         }
-        // technically [I::Item; N] is a dependently sized type, so transmute
-        // is out.
-        Some(unsafe { array_assume_init(array) })
+        Ok(items.map(|x| x.expect("all items were checked")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, rc::Rc};
+
+    #[test]
+    fn test_stream_input() {
+        let path = env::temp_dir().join(format!("aoc-stream-{}.txt", std::process::id()));
+        fs::write(&path, "1\n2\n\n3").unwrap();
+        let lines: Vec<String> = stream_input(&path).collect();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(lines, ["1", "2", "", "3"]);
+    }
+
+    #[test]
+    fn test_array_collect() {
+        assert_eq!((1..=3).array_collect(), Some([1, 2, 3]));
+        assert_eq!(
+            (1..=3).try_array_collect::<0>(),
+            Err(ArrayCollectError {
+                expected: 0,
+                found: 0,
+                leftovers: true,
+            })
+        );
+        assert_eq!(
+            (1..=3).try_array_collect::<4>(),
+            Err(ArrayCollectError {
+                expected: 4,
+                found: 3,
+                leftovers: false,
+            })
+        );
+        assert_eq!((1..=4).array_collect::<3>(), None);
+
+        // partially collected items are dropped
+        let rc = Rc::new(());
+        assert!(std::iter::repeat_n(rc.clone(), 2)
+            .array_collect::<3>()
+            .is_none());
+        assert_eq!(Rc::strong_count(&rc), 1);
     }
 }
@@ -0,0 +1,276 @@
+//! Number theory for cycle-lining-up and huge-step puzzles.
+//!
+//! The functions work on any primitive integer, doing their arithmetic in
+//! `i128` so intermediate products can't overflow. Inputs must fit in an
+//! `i128`, and results in `T`.
+
+use std::{
+    fmt,
+    ops::{Add, Mul, Sub},
+};
+
+use num_traits::{One, PrimInt, Signed, Zero};
+
+fn wide<T: PrimInt>(x: T) -> i128 {
+    x.to_i128().expect("number too big for i128")
+}
+
+fn narrow<T: PrimInt>(x: i128) -> T {
+    T::from(x).expect("result doesn't fit in the integer type")
+}
+
+/// `a + b mod m` for `a, b` in `[0, m)`, without overflowing.
+fn add_mod(a: i128, b: i128, m: i128) -> i128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// `a * b mod m` for `a, b` in `[0, m)`, without overflowing.
+fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    // double-and-add, keeping every intermediate below m
+    let (mut a, mut b, mut out) = (a, b, 0);
+    while b > 0 {
+        if b & 1 == 1 {
+            out = add_mod(out, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    out
+}
+
+fn gcd_wide(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+/// The greatest common divisor, always nonnegative
+pub fn gcd<T: PrimInt>(a: T, b: T) -> T {
+    narrow(gcd_wide(wide(a), wide(b)))
+}
+
+/// The least common multiple, always nonnegative
+pub fn lcm<T: PrimInt>(a: T, b: T) -> T {
+    let (a, b) = (wide(a), wide(b));
+    if a == 0 || b == 0 {
+        return T::zero();
+    }
+    let lcm = (a / gcd_wide(a, b))
+        .checked_mul(b)
+        .and_then(i128::checked_abs)
+        .expect("lcm overflows i128");
+    narrow(lcm)
+}
+
+fn extended_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `(g, x, y)` with `g = gcd(a, b)` and `a*x + b*y = g`
+pub fn extended_gcd<T: PrimInt + Signed>(a: T, b: T) -> (T, T, T) {
+    let (g, x, y) = extended_gcd_wide(wide(a), wide(b));
+    (narrow(g), narrow(x), narrow(y))
+}
+
+/// `base^exp mod m`, in `[0, m)`
+pub fn mod_pow<T: PrimInt>(base: T, exp: T, m: T) -> T {
+    let (base, mut exp, m) = (wide(base), wide(exp), wide(m));
+    assert!(m > 0, "modulus must be positive");
+    assert!(exp >= 0, "exponent must be nonnegative");
+    let mut base = base.rem_euclid(m);
+    let mut out = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            out = mul_mod(out, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    narrow(out)
+}
+
+fn mod_inverse_wide(a: i128, m: i128) -> Option<i128> {
+    let (g, x, _) = extended_gcd_wide(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// `x` in `[0, m)` with `a*x ≡ 1 (mod m)`, if `a` and `m` are coprime
+pub fn mod_inverse<T: PrimInt>(a: T, m: T) -> Option<T> {
+    let m = wide(m);
+    assert!(m > 0, "modulus must be positive");
+    mod_inverse_wide(wide(a), m).map(narrow)
+}
+
+/// Chinese Remainder Theorem: solve `y ≡ r (mod n)` for every `(r, n)` in
+/// `congruences`, giving `(x, m)` such that the solutions are exactly
+/// `y ≡ x (mod m)`, with `x` in `[0, m)`.
+///
+/// Moduli don't need to be coprime; if the congruences contradict each other,
+/// this returns `None`.
+pub fn crt<T: PrimInt>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let (mut x, mut m) = (0i128, 1i128);
+    for &(r, n) in congruences {
+        let n = wide(n);
+        assert!(n > 0, "modulus must be positive");
+        let r = wide(r).rem_euclid(n);
+        // solve x + m*k ≡ r (mod n) for k
+        let g = gcd_wide(m, n);
+        let diff = (r - x).rem_euclid(n);
+        if diff % g != 0 {
+            return None;
+        }
+        let n_g = n / g;
+        let inv = mod_inverse_wide(m / g, n_g).expect("m/g and n/g are coprime");
+        let k = mul_mod((diff / g) % n_g, inv, n_g);
+        let lcm = (m / g)
+            .checked_mul(n)
+            .expect("combined modulus overflows i128");
+        x = add_mod(x, mul_mod(m % lcm, k, lcm), lcm);
+        m = lcm;
+    }
+    Some((narrow(x), narrow(m)))
+}
+
+/// An integer mod `M`, for answers too big to keep whole.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ModInt<const M: u64>(u64);
+
+impl<const M: u64> ModInt<M> {
+    pub fn new(x: u64) -> Self {
+        ModInt(x % M)
+    }
+
+    /// The value, in `[0, M)`
+    pub fn get(self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(self, exp: u64) -> Self {
+        ModInt(mod_pow(self.0, exp, M))
+    }
+
+    /// The multiplicative inverse, if `self` and `M` are coprime
+    pub fn inverse(self) -> Option<Self> {
+        mod_inverse(self.0, M).map(ModInt)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        ModInt(((self.0 as u128 + rhs.0 as u128) % M as u128) as u64)
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        ModInt(((self.0 as u128 + M as u128 - rhs.0 as u128) % M as u128) as u64)
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        ModInt((self.0 as u128 * rhs.0 as u128 % M as u128) as u64)
+    }
+}
+
+impl<const M: u64> Zero for ModInt<M> {
+    fn zero() -> Self {
+        ModInt(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl<const M: u64> One for ModInt<M> {
+    fn one() -> Self {
+        ModInt::new(1)
+    }
+}
+
+impl<const M: u64> fmt::Debug for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {})", self.0, M)
+    }
+}
+
+impl<const M: u64> fmt::Display for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "lcm overflows i128")]
+    fn test_lcm_overflow() {
+        lcm(i128::MAX - 1, i128::MAX - 2);
+    }
+
+    #[test]
+    fn test_math() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(lcm(4u8, 6), 12);
+        assert_eq!(lcm(i128::MAX, i128::MAX), i128::MAX);
+        let (g, x, y) = extended_gcd(240i32, 46);
+        assert_eq!((g, 240 * x + 46 * y), (2, 2));
+        assert_eq!(mod_pow(4u64, 13, 497), 445);
+        assert_eq!(mod_pow(-2i32, 3, 5), 2);
+        // (2^64 - 59) is prime, so Fermat's little theorem holds
+        let p = u64::MAX - 58;
+        assert_eq!(mod_pow(123456789u64, p - 1, p), 1);
+        // and so is 2^127 - 1, where doubling would overflow
+        let p = i128::MAX;
+        assert_eq!(mod_pow((1 << 126) + 12345, p - 1, p), 1);
+        assert_eq!(mod_pow(p - 1, 2, p), 1);
+        assert_eq!(mod_inverse(3i64, 11), Some(4));
+        assert_eq!(mod_inverse(6i64, 9), None);
+        assert_eq!(crt(&[(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1u64, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1u64, 4), (2, 6)]), None);
+        assert_eq!(crt::<i32>(&[]), Some((0, 1)));
+        assert_eq!(
+            crt(&[(i128::MAX - 1, i128::MAX)]),
+            Some((i128::MAX - 1, i128::MAX))
+        );
+
+        type M7 = ModInt<7>;
+        assert_eq!(M7::new(5) + M7::new(4), M7::new(2));
+        assert_eq!(M7::new(2) - M7::new(5), M7::new(4));
+        assert_eq!(M7::new(3) * M7::new(5), M7::new(1));
+        assert_eq!(M7::new(3).inverse(), Some(M7::new(5)));
+        assert_eq!(M7::new(3).pow(6), M7::one());
+    }
+}
@@ -0,0 +1,100 @@
+//! Square matrices, mostly for raising to huge powers.
+//!
+//! A linear recurrence over `N` buckets is one matrix multiply per step, so
+//! `n` steps is one matrix raised to the `n`, which takes `O(log n)`
+//! multiplies.
+
+use std::ops::Mul;
+
+use num_traits::{One, Zero};
+
+/// An `N`x`N` matrix, indexed `[row][col]`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Matrix<T, const N: usize>(pub [[T; N]; N]);
+
+impl<T: Copy + Zero, const N: usize> Matrix<T, N> {
+    pub fn zero() -> Self {
+        Matrix([[T::zero(); N]; N])
+    }
+}
+
+impl<T: Copy + Zero + One, const N: usize> Matrix<T, N> {
+    pub fn identity() -> Self {
+        let mut m = Self::zero();
+        for i in 0..N {
+            m.0[i][i] = T::one();
+        }
+        m
+    }
+
+    /// `self` multiplied by itself `exp` times, by repeated squaring
+    pub fn pow(self, mut exp: u64) -> Self {
+        let mut base = self;
+        let mut out = Self::identity();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                out = out * base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base * base;
+            }
+        }
+        out
+    }
+}
+
+impl<T: Copy + Zero + Mul<Output = T>, const N: usize> Mul for Matrix<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let mut out = Self::zero();
+        for i in 0..N {
+            for k in 0..N {
+                let a = self.0[i][k];
+                if a.is_zero() {
+                    continue;
+                }
+                for j in 0..N {
+                    out.0[i][j] = out.0[i][j] + a * rhs.0[k][j];
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Apply the matrix to a column vector.
+impl<T: Copy + Zero + Mul<Output = T>, const N: usize> Mul<[T; N]> for Matrix<T, N> {
+    type Output = [T; N];
+
+    fn mul(self, v: [T; N]) -> [T; N] {
+        let mut out = [T::zero(); N];
+        for (row, x) in self.0.iter().zip(&mut out) {
+            *x = row
+                .iter()
+                .zip(&v)
+                .fold(T::zero(), |acc, (&a, &b)| acc + a * b);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ModInt;
+
+    #[test]
+    fn test_fibonacci() {
+        let fib = Matrix([[1u64, 1], [1, 0]]);
+        assert_eq!(fib.pow(0), Matrix::identity());
+        assert_eq!((fib.pow(10) * [1, 0])[1], 55);
+        assert_eq!(fib.pow(90).0[0][1], 2880067194370816120);
+
+        type M = ModInt<1_000_000_007>;
+        let fib = Matrix([[M::new(1), M::new(1)], [M::new(1), M::new(0)]]);
+        // F(10^15) mod 1e9+7
+        assert_eq!(fib.pow(1_000_000_000_000_000).0[0][1].get(), 648325137);
+    }
+}
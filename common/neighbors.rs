@@ -0,0 +1,122 @@
+//! Finding the neighbors of a cell in a 2D grid.
+
+use crate::Grid;
+
+/// Which surrounding cells count as neighbors
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Up, right, down, left
+    Orthogonal,
+    /// The four corners
+    Diagonal,
+    /// All eight surrounding cells
+    All,
+}
+
+impl Connectivity {
+    /// `(row, col)` offsets to each neighbor, clockwise from up
+    pub fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Connectivity::Orthogonal => &[(-1, 0), (0, 1), (1, 0), (0, -1)],
+            Connectivity::Diagonal => &[(-1, 1), (1, 1), (1, -1), (-1, -1)],
+            Connectivity::All => &[
+                (-1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+                (1, 0),
+                (1, -1),
+                (0, -1),
+                (-1, -1),
+            ],
+        }
+    }
+}
+
+/// What happens to neighbors past the edge of the grid
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edges {
+    /// They don't exist
+    Bounded,
+    /// They're pulled back onto the nearest edge cell, which may be the cell
+    /// itself or a repeat
+    Clamped,
+    /// They wrap around to the other side, like a torus
+    Wrapping,
+}
+
+/// Move `i` by `delta` along an axis of length `len`, if `edges` allows.
+fn step(i: usize, delta: isize, len: usize, edges: Edges) -> Option<usize> {
+    let moved = i as isize + delta;
+    match edges {
+        Edges::Bounded => usize::try_from(moved).ok().filter(|&x| x < len),
+        Edges::Clamped => Some(moved.clamp(0, len as isize - 1) as usize),
+        Edges::Wrapping => Some(moved.rem_euclid(len as isize) as usize),
+    }
+}
+
+/// The `(row, col)` of each neighbor of `pos` in a grid of shape `dim`, e.g.
+/// `neighbors(array.dim(), pos, Connectivity::Orthogonal, Edges::Bounded)`.
+pub fn neighbors(
+    dim: (usize, usize),
+    (row, col): (usize, usize),
+    connectivity: Connectivity,
+    edges: Edges,
+) -> impl Iterator<Item = (usize, usize)> {
+    let (nrows, ncols) = dim;
+    debug_assert!(
+        row < nrows && col < ncols,
+        "{:?} not in {:?}",
+        (row, col),
+        dim
+    );
+    connectivity.offsets().iter().filter_map(move |&(dr, dc)| {
+        Some((step(row, dr, nrows, edges)?, step(col, dc, ncols, edges)?))
+    })
+}
+
+impl<T> Grid<T> {
+    /// The `(row, col)` of each neighbor of `pos`
+    pub fn neighbors(
+        &self,
+        pos: (usize, usize),
+        connectivity: Connectivity,
+        edges: Edges,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        neighbors(self.0.dim(), pos, connectivity, edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(
+        pos: (usize, usize),
+        connectivity: Connectivity,
+        edges: Edges,
+    ) -> Vec<(usize, usize)> {
+        neighbors((3, 4), pos, connectivity, edges).collect()
+    }
+
+    #[test]
+    fn test_edges() {
+        use Connectivity::*;
+        use Edges::*;
+        assert_eq!(collect((0, 0), Orthogonal, Bounded), [(0, 1), (1, 0)]);
+        assert_eq!(collect((1, 1), All, Bounded).len(), 8);
+        assert_eq!(collect((0, 3), Diagonal, Bounded), [(1, 2)]);
+        assert_eq!(
+            collect((0, 0), Orthogonal, Clamped),
+            [(0, 0), (0, 1), (1, 0), (0, 0)]
+        );
+        assert_eq!(
+            collect((0, 0), Orthogonal, Wrapping),
+            [(2, 0), (0, 1), (1, 0), (0, 3)]
+        );
+        assert_eq!(
+            collect((2, 3), Diagonal, Wrapping),
+            [(1, 0), (0, 0), (0, 2), (1, 2)]
+        );
+    }
+}
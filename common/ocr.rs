@@ -0,0 +1,250 @@
+//! Reading answers drawn in AoC's block-letter fonts.
+
+use std::{error::Error, fmt, ops::Range};
+
+use ndarray::{s, Array2, ArrayView2, Axis};
+
+/// The 6-tall font, mostly 4 wide
+const SMALL: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// The 10-tall font, 6 wide
+const LARGE: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OcrError {
+    /// The lit rows aren't the height of either font
+    Height(usize),
+    /// Some glyphs didn't match any letter
+    Unrecognized {
+        /// What was read, with `?` for each unrecognized glyph
+        text: String,
+        /// The columns of each unrecognized glyph
+        glyphs: Vec<Range<usize>>,
+    },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::Height(h) => write!(f, "letters are {} tall, expected 6 or 10", h),
+            OcrError::Unrecognized { text, glyphs } => {
+                write!(
+                    f,
+                    "read {:?} but couldn't recognize glyphs at columns",
+                    text
+                )?;
+                for (i, cols) in glyphs.iter().enumerate() {
+                    let sep = if i == 0 { " " } else { ", " };
+                    write!(f, "{}{}..{}", sep, cols.start, cols.end)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for OcrError {}
+
+/// Draw a glyph the way the font tables do.
+fn glyph_text(glyph: ArrayView2<bool>) -> String {
+    let rows: Vec<String> = glyph
+        .rows()
+        .into_iter()
+        .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect())
+        .collect();
+    rows.join("\n")
+}
+
+/// Read the letters drawn by the lit cells of `grid`.
+///
+/// Letters are split apart at fully unlit columns, and any unlit border is
+/// ignored.
+pub fn ocr(grid: &Array2<bool>) -> Result<String, OcrError> {
+    let lit_rows: Vec<usize> = grid
+        .axis_iter(Axis(0))
+        .enumerate()
+        .filter(|(_, row)| row.iter().any(|&x| x))
+        .map(|(i, _)| i)
+        .collect();
+    let (top, bottom) = match (lit_rows.first(), lit_rows.last()) {
+        (Some(&top), Some(&bottom)) => (top, bottom + 1),
+        _ => return Ok(String::new()),
+    };
+    let font: &[(char, &str)] = match bottom - top {
+        6 => &SMALL,
+        10 => &LARGE,
+        h => return Err(OcrError::Height(h)),
+    };
+    let rows = grid.slice(s![top..bottom, ..]);
+    let lit_cols: Vec<bool> = rows
+        .axis_iter(Axis(1))
+        .map(|col| col.iter().any(|&x| x))
+        .collect();
+
+    let mut text = String::new();
+    let mut unrecognized = vec![];
+    let mut col = 0;
+    while col < lit_cols.len() {
+        if !lit_cols[col] {
+            col += 1;
+            continue;
+        }
+        let start = col;
+        while col < lit_cols.len() && lit_cols[col] {
+            col += 1;
+        }
+        let glyph = glyph_text(rows.slice(s![.., start..col]));
+        match font.iter().find(|(_, drawn)| *drawn == glyph) {
+            Some(&(c, _)) => text.push(c),
+            None => {
+                text.push('?');
+                unrecognized.push(start..col);
+            }
+        }
+    }
+    if unrecognized.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError::Unrecognized {
+            text,
+            glyphs: unrecognized,
+        })
+    }
+}
+
+/// Read letters drawn as text, with `#` for lit cells and anything else
+/// unlit.
+pub fn ocr_str(s: &str) -> Result<String, OcrError> {
+    let lines: Vec<&str> = s.lines().collect();
+    let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let mut grid = Array2::from_elem((lines.len(), width), false);
+    for (row, line) in lines.iter().enumerate() {
+        for (col, c) in line.chars().enumerate() {
+            grid[(row, col)] = c == '#';
+        }
+    }
+    ocr(&grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small() {
+        let drawn = "\
+..........................
+.#..#.####.#.....##..###..
+.#..#.#....#....#..#.#..#.
+.####.###..#....#..#.#..#.
+.#..#.#....#....#..#.###..
+.#..#.#....#....#..#.#.#..
+.#..#.####.####..##..#..#.";
+        assert_eq!(ocr_str(drawn), Ok("HELOR".to_string()));
+        assert_eq!(ocr_str(&drawn.replace('.', " ")), Ok("HELOR".to_string()));
+        assert_eq!(ocr_str(""), Ok(String::new()));
+        assert_eq!(ocr_str("#\n#\n#"), Err(OcrError::Height(3)));
+
+        let broken = drawn.replacen(".####.###", ".####.##.", 1);
+        match ocr_str(&broken) {
+            Err(OcrError::Unrecognized { text, glyphs }) => {
+                assert_eq!(text, "H?LOR");
+                assert_eq!(glyphs.len(), 1);
+                assert_eq!(glyphs[0], 6..10);
+            }
+            x => panic!("misread {:?}", x),
+        }
+    }
+
+    #[test]
+    fn test_large() {
+        let (_, x) = LARGE.iter().find(|(c, _)| *c == 'X').unwrap();
+        let (_, n) = LARGE.iter().find(|(c, _)| *c == 'N').unwrap();
+        let drawn: Vec<String> = x
+            .lines()
+            .zip(n.lines())
+            .map(|(x, n)| format!("{}..{}", x, n))
+            .collect();
+        assert_eq!(ocr_str(&drawn.join("\n")), Ok("XN".to_string()));
+    }
+}
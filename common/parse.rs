@@ -0,0 +1,235 @@
+//! Pulling typed values out of lines of puzzle input.
+
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::{ArrayCollect, ArrayCollectError};
+
+/// Why a line didn't match a [`parse_line!`](crate::parse_line) pattern
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseLineError {
+    /// The text around the fields didn't match
+    Literal {
+        /// Byte offset in the line where the text was expected
+        pos: usize,
+        expected: String,
+    },
+    /// A field didn't parse as its type
+    Field {
+        /// Which `{}` in the pattern, from 0
+        index: usize,
+        /// Byte offset of the field in the line
+        pos: usize,
+        text: String,
+        error: String,
+    },
+}
+
+impl fmt::Display for ParseLineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseLineError::Literal { pos, expected } => {
+                write!(f, "expected {:?} at column {}", expected, pos + 1)
+            }
+            ParseLineError::Field {
+                index,
+                pos,
+                text,
+                error,
+            } => write!(
+                f,
+                "field {} {:?} at column {}: {}",
+                index,
+                text,
+                pos + 1,
+                error
+            ),
+        }
+    }
+}
+
+impl Error for ParseLineError {}
+
+/// Match `line` against `pattern`, returning the byte offset and text of
+/// each `{}` field.
+///
+/// A field takes everything up to the first place the text after it in the
+/// pattern matches, or the rest of the line if it's last.
+pub fn match_pattern<'a>(
+    line: &'a str,
+    pattern: &str,
+) -> Result<Vec<(usize, &'a str)>, ParseLineError> {
+    let literals: Vec<&str> = pattern.split("{}").collect();
+    let expect = |pos: usize, expected: &str| ParseLineError::Literal {
+        pos,
+        expected: expected.to_string(),
+    };
+    let (first, rest) = literals.split_first().expect("split is never empty");
+    if !line.starts_with(first) {
+        return Err(expect(0, first));
+    }
+    let mut pos = first.len();
+    let mut fields = Vec::with_capacity(rest.len());
+    for (i, literal) in rest.iter().enumerate() {
+        let remaining = &line[pos..];
+        let field_len = if i == rest.len() - 1 {
+            // the last field runs up to whatever ends the line
+            remaining
+                .strip_suffix(literal)
+                .ok_or_else(|| expect(line.len().saturating_sub(literal.len()), literal))?
+                .len()
+        } else {
+            assert!(
+                !literal.is_empty(),
+                "fields in {:?} need text between them",
+                pattern
+            );
+            remaining
+                .find(literal)
+                .ok_or_else(|| expect(pos, literal))?
+        };
+        fields.push((pos, &remaining[..field_len]));
+        pos += field_len + literal.len();
+    }
+    if rest.is_empty() && line.len() != pos {
+        return Err(expect(0, first));
+    }
+    Ok(fields)
+}
+
+/// Parse a line against a pattern with `{}` for each field, like
+/// `parse_line!(s, "{},{} -> {},{}", i32, i32, i32, i32)`, giving a
+/// `Result` of a tuple of the fields.
+#[macro_export]
+macro_rules! parse_line {
+    ($line:expr, $pattern:expr, $($t:ty),+ $(,)?) => {
+        $crate::match_pattern($line, $pattern).and_then(|fields| {
+            assert_eq!(
+                fields.len(),
+                [$(stringify!($t)),+].len(),
+                "pattern fields don't match the types given"
+            );
+            let mut fields = fields.into_iter().enumerate();
+            Ok(($({
+                let (index, (pos, text)) = fields.next().expect("checked the count");
+                text.parse::<$t>()
+                    .map_err(|e| $crate::ParseLineError::Field {
+                        index,
+                        pos,
+                        text: text.to_string(),
+                        error: e.to_string(),
+                    })?
+            },)+))
+        })
+    };
+}
+
+/// Every integer in `s`, ignoring whatever's around them. A `-` right
+/// before a digit makes it negative.
+///
+/// Panics if a number doesn't fit in `T`.
+pub fn ints<T>(s: &str) -> Vec<T>
+where
+    T: FromStr,
+    T::Err: fmt::Debug,
+{
+    let bytes = s.as_bytes();
+    let mut ints = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let negative = bytes[i] == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+        if !negative && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        i += 1;
+        while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+            i += 1;
+        }
+        let number = &s[start..i];
+        ints.push(
+            number
+                .parse()
+                .unwrap_or_else(|e| panic!("can't fit {}: {:?}", number, e)),
+        );
+    }
+    ints
+}
+
+/// Exactly `N` integers from `s`, as with [`ints`].
+pub fn ints_array<T, const N: usize>(s: &str) -> Result<[T; N], ArrayCollectError>
+where
+    T: FromStr,
+    T::Err: fmt::Debug,
+{
+    ints(s).into_iter().try_array_collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point2;
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            parse_line!("0,9 -> 5,9", "{},{} -> {},{}", i32, i32, i32, i32),
+            Ok((0, 9, 5, 9))
+        );
+        assert_eq!(
+            parse_line!("forward 5", "{} {}", String, u8),
+            Ok(("forward".to_string(), 5))
+        );
+        assert_eq!(
+            parse_line!("0,9 -> 5,9", "{} -> {}", Point2<i32>, Point2<i32>),
+            Ok((Point2::new(0, 9), Point2::new(5, 9)))
+        );
+        assert_eq!(
+            parse_line!("target area: x=20..30", "target area: x={}..{}", i32, i32),
+            Ok((20, 30))
+        );
+
+        assert_eq!(
+            parse_line!("0,9 => 5,9", "{},{} -> {},{}", i32, i32, i32, i32),
+            Err(ParseLineError::Literal {
+                pos: 2,
+                expected: " -> ".to_string()
+            })
+        );
+        assert_eq!(
+            parse_line!("x=1.", "y={}", i32),
+            Err(ParseLineError::Literal {
+                pos: 0,
+                expected: "y=".to_string()
+            })
+        );
+        match parse_line!("0,x -> 5,9", "{},{} -> {},{}", i32, i32, i32, i32) {
+            Err(ParseLineError::Field {
+                index, pos, text, ..
+            }) => assert_eq!((index, pos, text.as_str()), (1, 2, "x")),
+            x => panic!("misparsed {:?}", x),
+        }
+    }
+
+    #[test]
+    fn test_ints() {
+        assert_eq!(
+            ints_array("target area: x=20..30, y=-10..-5"),
+            Ok([20, 30, -10, -5])
+        );
+        assert_eq!(
+            ints::<i64>("on x=-54112..-39298,y=-85059..-49293,z=-27449..7877"),
+            [-54112, -39298, -85059, -49293, -27449, 7877]
+        );
+        assert_eq!(ints::<u8>("3,4,3,1,2\n"), [3, 4, 3, 1, 2]);
+        assert_eq!(ints::<i32>("a - b -- -c"), []);
+        assert_eq!(
+            ints_array::<i32, 2>("1 2 3"),
+            Err(ArrayCollectError {
+                expected: 2,
+                found: 2,
+                leftovers: true
+            })
+        );
+    }
+}
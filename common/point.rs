@@ -0,0 +1,211 @@
+//! 2D and 3D points and the vector arithmetic on them.
+
+use std::{
+    error::Error,
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use num_traits::{PrimInt, Signed};
+
+use crate::{ArrayCollect, ArrayCollectError};
+
+/// A point in 2D, with `y` growing downwards when used on a grid
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParsePointError<E> {
+    /// There weren't the right number of comma-separated coordinates
+    Shape(ArrayCollectError),
+    /// A coordinate didn't parse
+    Coord(E),
+}
+
+impl<E: fmt::Display> fmt::Display for ParsePointError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParsePointError::Shape(e) => write!(f, "wrong number of coordinates: {}", e),
+            ParsePointError::Coord(e) => write!(f, "invalid coordinate: {}", e),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> Error for ParsePointError<E> {}
+
+/// Parse exactly `N` comma-separated coordinates.
+fn parse_coords<T: FromStr, const N: usize>(s: &str) -> Result<[T; N], ParsePointError<T::Err>> {
+    let coords: [&str; N] = s
+        .split(',')
+        .try_array_collect()
+        .map_err(ParsePointError::Shape)?;
+    let parsed = coords
+        .iter()
+        .map(|c| c.trim().parse())
+        .collect::<Result<Vec<T>, _>>()
+        .map_err(ParsePointError::Coord)?;
+    Ok(parsed
+        .into_iter()
+        .array_collect()
+        .expect("one coordinate per part"))
+}
+
+macro_rules! impl_point {
+    ($Point:ident, $($c:ident),+) => {
+        impl<T> $Point<T> {
+            pub const fn new($($c: T),+) -> Self {
+                $Point { $($c),+ }
+            }
+        }
+
+        impl<T: Copy + Signed + PartialOrd> $Point<T> {
+            /// The taxicab distance: the sum of the distances along each axis
+            pub fn manhattan(self, other: Self) -> T {
+                let d = self - other;
+                T::zero() $(+ d.$c.abs())+
+            }
+
+            /// The king's-move distance: the largest distance along any axis
+            pub fn chebyshev(self, other: Self) -> T {
+                let d = self - other;
+                let mut max = T::zero();
+                $(
+                    if d.$c.abs() > max {
+                        max = d.$c.abs();
+                    }
+                )+
+                max
+            }
+
+            /// Each coordinate's sign: a single step towards the direction
+            /// of `self`, diagonal included
+            pub fn signum(self) -> Self {
+                $Point { $($c: self.$c.signum()),+ }
+            }
+        }
+
+        impl<T: Add<Output = T>> Add for $Point<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                $Point { $($c: self.$c + rhs.$c),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $Point<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                $Point { $($c: self.$c - rhs.$c),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $Point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $Point { $($c: -self.$c),+ }
+            }
+        }
+
+        /// Scale by a number
+        impl<T: Mul<Output = T> + Copy> Mul<T> for $Point<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                $Point { $($c: self.$c * rhs),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $Point<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$c += rhs.$c;)+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $Point<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$c -= rhs.$c;)+
+            }
+        }
+
+        /// Parses `x,y` or `x,y,z`, allowing spaces around coordinates.
+        impl<T: FromStr> FromStr for $Point<T> {
+            type Err = ParsePointError<T::Err>;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let [$($c),+] = parse_coords(s)?;
+                Ok($Point { $($c),+ })
+            }
+        }
+
+        impl<T: fmt::Display> fmt::Display for $Point<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let coords = [$(self.$c.to_string()),+];
+                f.write_str(&coords.join(","))
+            }
+        }
+    };
+}
+
+impl_point!(Point2, x, y);
+impl_point!(Point3, x, y, z);
+
+impl<T: PrimInt> Point2<T> {
+    /// The `(row, col)` of this point in an `Array2`, if neither coordinate
+    /// is negative
+    pub fn to_index(self) -> Option<(usize, usize)> {
+        Some((self.y.to_usize()?, self.x.to_usize()?))
+    }
+
+    /// The point at `(row, col)` in an `Array2`
+    pub fn from_index((row, col): (usize, usize)) -> Option<Self> {
+        Some(Point2 {
+            x: T::from(col)?,
+            y: T::from(row)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_points() {
+        let a: Point2<i32> = "0,9".parse().unwrap();
+        let b = Point2::new(5, 3);
+        assert_eq!(a + b, Point2::new(5, 12));
+        assert_eq!((b - a).signum(), Point2::new(1, -1));
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(b * 2, Point2::new(10, 6));
+        assert_eq!(b.to_index(), Some((3, 5)));
+        assert_eq!((-b).to_index(), None);
+        assert_eq!(Point2::from_index((3, 5)), Some(b));
+        assert_eq!(b.to_string(), "5,3");
+
+        let c: Point3<i64> = "-1, 2,3".parse().unwrap();
+        assert_eq!(c, Point3::new(-1, 2, 3));
+        assert_eq!(c.manhattan(Point3::default()), 6);
+        assert!(matches!(
+            "1,2".parse::<Point3<i64>>(),
+            Err(ParsePointError::Shape(_))
+        ));
+        assert!(matches!(
+            "1,x".parse::<Point2<i64>>(),
+            Err(ParsePointError::Coord(_))
+        ));
+    }
+}
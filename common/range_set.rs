@@ -0,0 +1,229 @@
+//! Sets of integers stored as disjoint intervals.
+
+use std::{
+    iter::FromIterator,
+    ops::{Bound, RangeBounds, RangeInclusive},
+};
+
+use num_traits::PrimInt;
+
+/// A set of integers, kept as sorted, disjoint, non-adjacent inclusive
+/// ranges.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        RangeSet { ranges: vec![] }
+    }
+}
+
+/// `range` as inclusive `(start, end)`, or `None` if it's empty
+fn bounds<T: PrimInt>(range: impl RangeBounds<T>) -> Option<(T, T)> {
+    let start = match range.start_bound() {
+        Bound::Included(&s) => s,
+        Bound::Excluded(&s) => s.checked_add(&T::one())?,
+        Bound::Unbounded => T::min_value(),
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&e) => e,
+        Bound::Excluded(&e) => e.checked_sub(&T::one())?,
+        Bound::Unbounded => T::max_value(),
+    };
+    (start <= end).then_some((start, end))
+}
+
+/// Whether a range ending at `end` overlaps or touches one starting at `start`
+fn touches<T: PrimInt>(end: T, start: T) -> bool {
+    end >= start || end.checked_add(&T::one()) == Some(start)
+}
+
+impl<T: PrimInt> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add every value in `range`
+    pub fn insert(&mut self, range: impl RangeBounds<T>) {
+        let Some((start, end)) = bounds(range) else {
+            return;
+        };
+        let i = self.ranges.partition_point(|&(_, e)| !touches(e, start));
+        let j = self.ranges.partition_point(|&(s, _)| touches(end, s));
+        let merged = match self.ranges.get(i..j) {
+            Some([first, .., last]) | Some([first @ last]) => (first.0.min(start), last.1.max(end)),
+            _ => (start, end),
+        };
+        self.ranges.splice(i..j, [merged]);
+    }
+
+    /// Take away every value in `range`
+    pub fn remove(&mut self, range: impl RangeBounds<T>) {
+        let Some((start, end)) = bounds(range) else {
+            return;
+        };
+        let i = self.ranges.partition_point(|&(_, e)| e < start);
+        let j = self.ranges.partition_point(|&(s, _)| s <= end);
+        if i >= j {
+            return;
+        }
+        let mut kept = Vec::with_capacity(2);
+        let (first, last) = (self.ranges[i], self.ranges[j - 1]);
+        if first.0 < start {
+            kept.push((first.0, start - T::one()));
+        }
+        if last.1 > end {
+            kept.push((end + T::one(), last.1));
+        }
+        self.ranges.splice(i..j, kept);
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        let i = self.ranges.partition_point(|&(_, e)| e < x);
+        self.ranges.get(i).is_some_and(|&(s, _)| s <= x)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// How many values are in the set, saturating at `u128::MAX` for all of
+    /// `i128` or `u128`
+    pub fn len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|&(s, e)| {
+                let span = match (s.to_i128(), e.to_i128()) {
+                    // e - s can overflow i128, but never u128
+                    (Some(s), Some(e)) => (e as u128).wrapping_sub(s as u128),
+                    // only u128 doesn't fit, and it can't underflow
+                    _ => (e - s).to_u128().expect("unsigned span"),
+                };
+                span.saturating_add(1)
+            })
+            .sum()
+    }
+
+    /// Values in either set
+    pub fn union(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut out = self.clone();
+        for r in other.ranges() {
+            out.insert(r);
+        }
+        out
+    }
+
+    /// Values in both sets
+    pub fn intersection(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        let mut ranges = vec![];
+        while let (Some(&&(s1, e1)), Some(&&(s2, e2))) = (a.peek(), b.peek()) {
+            let (start, end) = (s1.max(s2), e1.min(e2));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            // the range ending first can't overlap anything else
+            if e1 < e2 {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        RangeSet { ranges }
+    }
+
+    /// Values in `self` but not `other`
+    pub fn difference(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut out = self.clone();
+        for r in other.ranges() {
+            out.remove(r);
+        }
+        out
+    }
+
+    /// The disjoint ranges making up the set, in order
+    pub fn ranges(&self) -> impl DoubleEndedIterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(s, e)| s..=e)
+    }
+
+    /// Every value in the set, in order
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        self.ranges.iter().flat_map(|&(s, e)| {
+            let mut next = Some(s);
+            std::iter::from_fn(move || {
+                let x = next?;
+                next = if x < e { Some(x + T::one()) } else { None };
+                Some(x)
+            })
+        })
+    }
+}
+
+impl<T: PrimInt, R: RangeBounds<T>> FromIterator<R> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = R>>(iter: I) -> Self {
+        let mut out = RangeSet::new();
+        for r in iter {
+            out.insert(r);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(set: &RangeSet<i32>) -> Vec<RangeInclusive<i32>> {
+        set.ranges().collect()
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set = RangeSet::new();
+        set.insert(1..=3);
+        set.insert(10..20);
+        set.insert(5..=5);
+        assert_eq!(ranges(&set), [1..=3, 5..=5, 10..=19]);
+        set.insert(4..=4);
+        assert_eq!(ranges(&set), [1..=5, 10..=19]);
+        set.insert(0..=30);
+        assert_eq!(ranges(&set), [0..=30]);
+        set.remove(5..=9);
+        set.remove(30..);
+        assert_eq!(ranges(&set), [0..=4, 10..=29]);
+        assert!(set.contains(4) && !set.contains(5) && set.contains(10));
+        assert_eq!(set.len(), 25);
+        assert_eq!(
+            set.iter().take(7).collect::<Vec<_>>(),
+            [0, 1, 2, 3, 4, 10, 11]
+        );
+
+        let mut full = RangeSet::<u8>::new();
+        full.insert(..);
+        full.insert(3..=7);
+        assert_eq!(full.len(), 256);
+        assert_eq!(full.iter().last(), Some(255));
+
+        let mut wide = RangeSet::<i128>::new();
+        wide.insert(..0);
+        assert_eq!(wide.len(), 1 << 127);
+        wide.insert(..);
+        assert_eq!(wide.len(), u128::MAX);
+        assert_eq!(RangeSet::<u128>::from_iter([1..]).len(), u128::MAX);
+    }
+
+    #[test]
+    fn test_set_ops() {
+        let a: RangeSet<i32> = [0..=10, 20..=30].into_iter().collect();
+        let b: RangeSet<i32> = [5..=25].into_iter().collect();
+        assert_eq!(ranges(&a.union(&b)), [0..=30]);
+        assert_eq!(ranges(&a.intersection(&b)), [5..=10, 20..=25]);
+        assert_eq!(ranges(&a.difference(&b)), [0..=4, 26..=30]);
+        assert_eq!(ranges(&b.difference(&a)), [11..=19]);
+    }
+}
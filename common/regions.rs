@@ -0,0 +1,141 @@
+//! Flood fill and connected regions of a 2D grid.
+
+use ndarray::Array2;
+
+use crate::{neighbors, Connectivity, Edges};
+
+/// One connected region found by [`label_components`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Component {
+    /// Number of cells in the region
+    pub size: usize,
+    /// Top-left `(row, col)` of the region's bounding box, inclusive
+    pub min: (usize, usize),
+    /// Bottom-right `(row, col)` of the region's bounding box, inclusive
+    pub max: (usize, usize),
+}
+
+/// Every cell's region label, indexing into `components`
+#[derive(Clone, Debug)]
+pub struct Labels {
+    pub labels: Array2<usize>,
+    pub components: Vec<Component>,
+}
+
+/// Visit every cell connected to `seed`, including `seed` itself.
+///
+/// Two neighboring cells are connected if `same(a, b)` holds for them.
+fn fill<T>(
+    grid: &Array2<T>,
+    seed: (usize, usize),
+    connectivity: Connectivity,
+    same: &mut impl FnMut(&T, &T) -> bool,
+    seen: &mut Array2<bool>,
+    mut visit: impl FnMut((usize, usize)),
+) {
+    let mut stack = vec![seed];
+    seen[seed] = true;
+    while let Some(pos) = stack.pop() {
+        visit(pos);
+        for next in neighbors(grid.dim(), pos, connectivity, Edges::Bounded) {
+            if !seen[next] && same(&grid[pos], &grid[next]) {
+                seen[next] = true;
+                stack.push(next);
+            }
+        }
+    }
+}
+
+/// The `(row, col)` of every cell in the region containing `seed`, where
+/// neighboring cells are in the same region if `same(a, b)`.
+pub fn flood_fill<T>(
+    grid: &Array2<T>,
+    seed: (usize, usize),
+    connectivity: Connectivity,
+    mut same: impl FnMut(&T, &T) -> bool,
+) -> Vec<(usize, usize)> {
+    let mut seen = Array2::from_elem(grid.dim(), false);
+    let mut region = vec![];
+    fill(grid, seed, connectivity, &mut same, &mut seen, |pos| {
+        region.push(pos)
+    });
+    region
+}
+
+/// Split the whole grid into regions, where neighboring cells are in the
+/// same region if `same(a, b)`.
+///
+/// Labels are handed out in row-major order of each region's first cell.
+pub fn label_components<T>(
+    grid: &Array2<T>,
+    connectivity: Connectivity,
+    mut same: impl FnMut(&T, &T) -> bool,
+) -> Labels {
+    let mut seen = Array2::from_elem(grid.dim(), false);
+    let mut labels = Array2::zeros(grid.dim());
+    let mut components = vec![];
+    for seed in grid.indexed_iter().map(|(pos, _)| pos) {
+        if seen[seed] {
+            continue;
+        }
+        let label = components.len();
+        let mut component = Component {
+            size: 0,
+            min: seed,
+            max: seed,
+        };
+        fill(
+            grid,
+            seed,
+            connectivity,
+            &mut same,
+            &mut seen,
+            |(row, col)| {
+                labels[(row, col)] = label;
+                component.size += 1;
+                component.min = (component.min.0.min(row), component.min.1.min(col));
+                component.max = (component.max.0.max(row), component.max.1.max(col));
+            },
+        );
+        components.push(component);
+    }
+    Labels { labels, components }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grid;
+
+    const HEIGHTS: &str = "\
+2199943210
+3987894921
+9856789892
+8767896789
+9899965678";
+
+    #[test]
+    fn test_basins() {
+        let heights = Grid::parse(HEIGHTS, |c| c.to_digit(10)).unwrap().0;
+        let basin = |&a: &u32, &b: &u32| a != 9 && b != 9;
+
+        let mut top_left = flood_fill(&heights, (0, 0), Connectivity::Orthogonal, basin);
+        top_left.sort_unstable();
+        assert_eq!(top_left, [(0, 0), (0, 1), (1, 0)]);
+
+        let Labels { labels, components } =
+            label_components(&heights, Connectivity::Orthogonal, basin);
+        assert_eq!(labels[(0, 0)], labels[(1, 0)]);
+        assert_ne!(labels[(0, 0)], labels[(0, 9)]);
+        let mut sizes: Vec<usize> = components
+            .iter()
+            .filter(|c| c.size > 1)
+            .map(|c| c.size)
+            .collect();
+        sizes.sort_unstable();
+        assert_eq!(sizes, [3, 9, 9, 14]);
+        let top_right = components[labels[(0, 9)]];
+        assert_eq!(top_right.min, (0, 5));
+        assert_eq!(top_right.max, (2, 9));
+    }
+}
@@ -0,0 +1,206 @@
+//! Configurable text rendering of 2D arrays, for when [`Dots`](crate::Dots)
+//! isn't enough.
+
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Write},
+};
+
+use ndarray::Array2;
+
+/// A terminal color
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// Write the ANSI escape selecting this color, as foreground if `fg`
+    fn write_escape(self, f: &mut impl Write, fg: bool) -> fmt::Result {
+        let base = if fg { 30 } else { 40 };
+        let index = match self {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::White => 7,
+            Color::Rgb(r, g, b) => return write!(f, "\x1b[{};2;{};{};{}m", base + 8, r, g, b),
+        };
+        write!(f, "\x1b[{}m", base + index)
+    }
+
+    /// Blue for `t = 0` through green to red for `t = 1`
+    pub fn heat(t: f64) -> Color {
+        let t = if t.is_nan() { 0. } else { t.clamp(0., 1.) };
+        let channel = |x: f64| (x.clamp(0., 1.) * 255.).round() as u8;
+        Color::Rgb(
+            channel(2. * t - 1.),
+            channel(1. - (2. * t - 1.).abs()),
+            channel(1. - 2. * t),
+        )
+    }
+}
+
+type Styler<'a, T> = Box<dyn Fn(&T) -> Option<Color> + 'a>;
+type Heat<'a, T> = Box<dyn Fn(&T) -> f64 + 'a>;
+
+/// Renders each cell of an array as one glyph, built up like
+/// `Renderer::new(&map, |&x| if x { '#' } else { '.' }).axes()`.
+pub struct Renderer<'a, T> {
+    array: &'a Array2<T>,
+    glyph: Box<dyn Fn(&T) -> char + 'a>,
+    fg: Option<Styler<'a, T>>,
+    bg: Option<Styler<'a, T>>,
+    heat: Option<Heat<'a, T>>,
+    highlights: HashSet<(usize, usize)>,
+    axes: bool,
+}
+
+impl<'a, T> Renderer<'a, T> {
+    pub fn new(array: &'a Array2<T>, glyph: impl Fn(&T) -> char + 'a) -> Self {
+        Renderer {
+            array,
+            glyph: Box::new(glyph),
+            fg: None,
+            bg: None,
+            heat: None,
+            highlights: HashSet::new(),
+            axes: false,
+        }
+    }
+
+    /// Color each glyph, or leave it the default color for `None`
+    pub fn fg(mut self, color: impl Fn(&T) -> Option<Color> + 'a) -> Self {
+        self.fg = Some(Box::new(color));
+        self
+    }
+
+    /// Color behind each glyph, or leave it the default for `None`
+    pub fn bg(mut self, color: impl Fn(&T) -> Option<Color> + 'a) -> Self {
+        self.bg = Some(Box::new(color));
+        self
+    }
+
+    /// Shade the background from blue to red by `value`, scaled between the
+    /// smallest and largest value in the array. Replaces [`Renderer::bg`].
+    pub fn heatmap(mut self, value: impl Fn(&T) -> f64 + 'a) -> Self {
+        self.heat = Some(Box::new(value));
+        self
+    }
+
+    /// Show the cells at these `(row, col)`s in inverse video
+    pub fn highlight(mut self, positions: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        self.highlights.extend(positions);
+        self
+    }
+
+    /// Label rows down the left and columns, with stacked digits, across the
+    /// top
+    pub fn axes(mut self) -> Self {
+        self.axes = true;
+        self
+    }
+
+    fn write_column_labels(&self, f: &mut fmt::Formatter<'_>, row_width: usize) -> fmt::Result {
+        let ncols = self.array.ncols();
+        let digits = ncols.saturating_sub(1).to_string().len();
+        for place in (0..digits).rev() {
+            write!(f, "{:w$} ", "", w = row_width)?;
+            for col in 0..ncols {
+                let digit = col / 10usize.pow(place as u32);
+                if digit == 0 && place > 0 {
+                    f.write_char(' ')?;
+                } else {
+                    write!(f, "{}", digit % 10)?;
+                }
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T> Display for Renderer<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let heat_range = self.heat.as_ref().map(|heat| {
+            self.array
+                .iter()
+                .map(heat)
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), x| {
+                    (lo.min(x), hi.max(x))
+                })
+        });
+        let row_width = self.array.nrows().saturating_sub(1).to_string().len();
+        if self.axes {
+            self.write_column_labels(f, row_width)?;
+        }
+        for (row, cells) in self.array.rows().into_iter().enumerate() {
+            if self.axes {
+                write!(f, "{:>w$} ", row, w = row_width)?;
+            }
+            for (col, e) in cells.iter().enumerate() {
+                let fg = self.fg.as_ref().and_then(|fg| fg(e));
+                let bg = match (&self.heat, heat_range) {
+                    (Some(heat), Some((lo, hi))) => Some(Color::heat((heat(e) - lo) / (hi - lo))),
+                    _ => self.bg.as_ref().and_then(|bg| bg(e)),
+                };
+                let highlight = self.highlights.contains(&(row, col));
+                if let Some(fg) = fg {
+                    fg.write_escape(f, true)?;
+                }
+                if let Some(bg) = bg {
+                    bg.write_escape(f, false)?;
+                }
+                if highlight {
+                    f.write_str("\x1b[7m")?;
+                }
+                f.write_char((self.glyph)(e))?;
+                if fg.is_some() || bg.is_some() || highlight {
+                    f.write_str("\x1b[0m")?;
+                }
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_render() {
+        let a = array![[false, true], [true, false]];
+        let glyph = |&x: &bool| if x { '#' } else { '.' };
+        assert_eq!(Renderer::new(&a, glyph).to_string(), ".#\n#.\n");
+        assert_eq!(
+            Renderer::new(&a, glyph).highlight([(1, 1)]).to_string(),
+            ".#\n#\x1b[7m.\x1b[0m\n"
+        );
+        assert_eq!(
+            Renderer::new(&a, glyph)
+                .fg(|&x| x.then_some(Color::Red))
+                .to_string(),
+            ".\x1b[31m#\x1b[0m\n\x1b[31m#\x1b[0m.\n"
+        );
+
+        let wide = Array2::from_elem((2, 12), 0);
+        assert_eq!(
+            Renderer::new(&wide, |_| '.').axes().to_string(),
+            "            11\n  012345678901\n0 ............\n1 ............\n"
+        );
+    }
+}
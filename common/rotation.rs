@@ -0,0 +1,126 @@
+//! The 24 ways to turn an axis-aligned 3D object without mirroring it.
+
+use std::ops::Neg;
+
+use num_traits::{One, Zero};
+
+use crate::{Matrix, Point3};
+
+/// An axis-aligned rotation, as a signed permutation of the axes: coordinate
+/// `i` of the result is coordinate `perm[i]` of the input, times `sign[i]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rotation {
+    perm: [usize; 3],
+    sign: [i8; 3],
+}
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation {
+        perm: [0, 1, 2],
+        sign: [1, 1, 1],
+    };
+
+    /// Every proper rotation, starting with [`Rotation::IDENTITY`]
+    pub fn all() -> [Rotation; 24] {
+        const PERMS: [([usize; 3], i8); 6] = [
+            ([0, 1, 2], 1),
+            ([1, 2, 0], 1),
+            ([2, 0, 1], 1),
+            ([0, 2, 1], -1),
+            ([2, 1, 0], -1),
+            ([1, 0, 2], -1),
+        ];
+        let mut out = [Rotation::IDENTITY; 24];
+        let mut n = 0;
+        for (perm, parity) in PERMS {
+            for signs in 0..8 {
+                let sign = [0, 1, 2].map(|i| if signs >> i & 1 == 0 { 1 } else { -1 });
+                // mirror images have a determinant of -1
+                if parity * sign.iter().product::<i8>() == 1 {
+                    out[n] = Rotation { perm, sign };
+                    n += 1;
+                }
+            }
+        }
+        out
+    }
+
+    pub fn apply<T: Copy + Neg<Output = T>>(self, p: Point3<T>) -> Point3<T> {
+        let coords = [p.x, p.y, p.z];
+        let [x, y, z] = [0, 1, 2].map(|i| {
+            let c = coords[self.perm[i]];
+            if self.sign[i] < 0 {
+                -c
+            } else {
+                c
+            }
+        });
+        Point3 { x, y, z }
+    }
+
+    /// Rotate every point in `points`
+    pub fn apply_all<T: Copy + Neg<Output = T>>(
+        self,
+        points: &[Point3<T>],
+    ) -> impl Iterator<Item = Point3<T>> + '_ {
+        points.iter().map(move |&p| self.apply(p))
+    }
+
+    /// The rotation doing `self` and then `next`
+    pub fn then(self, next: Rotation) -> Rotation {
+        Rotation {
+            perm: next.perm.map(|p| self.perm[p]),
+            sign: [0, 1, 2].map(|i| next.sign[i] * self.sign[next.perm[i]]),
+        }
+    }
+
+    /// The rotation undoing `self`
+    pub fn inverse(self) -> Rotation {
+        let mut inv = Rotation::IDENTITY;
+        for i in 0..3 {
+            inv.perm[self.perm[i]] = i;
+            inv.sign[self.perm[i]] = self.sign[i];
+        }
+        inv
+    }
+
+    /// The rotation as a matrix acting on column vectors
+    pub fn to_matrix<T: Copy + Zero + One + Neg<Output = T>>(self) -> Matrix<T, 3> {
+        let mut m = Matrix::zero();
+        for i in 0..3 {
+            m.0[i][self.perm[i]] = if self.sign[i] < 0 {
+                -T::one()
+            } else {
+                T::one()
+            };
+        }
+        m
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_group() {
+        let all = Rotation::all();
+        assert_eq!(all.iter().collect::<HashSet<_>>().len(), 24);
+        let p = Point3::new(1, 2, 3);
+        assert_eq!(
+            all.iter().map(|r| r.apply(p)).collect::<HashSet<_>>().len(),
+            24
+        );
+        for a in all {
+            assert_eq!(a.then(a.inverse()), Rotation::IDENTITY);
+            assert_eq!(a.inverse().apply(a.apply(p)), p);
+            let [x, y, z] = a.to_matrix::<i32>() * [1, 2, 3];
+            assert_eq!(a.apply(p), Point3::new(x, y, z));
+            for b in all {
+                assert!(all.contains(&a.then(b)));
+                assert_eq!(a.then(b).apply(p), b.apply(a.apply(p)));
+            }
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! Graph searches over implicit graphs, described by a successor function.
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+use num_traits::Zero;
+
+/// The cheapest way found from the start to a goal
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Route<N, C> {
+    pub cost: C,
+    /// Every node visited, from the start to the goal inclusive
+    pub path: Vec<N>,
+}
+
+/// Nodes seen so far, stored once and referred to by index.
+struct Visited<N, C> {
+    index: HashMap<N, usize>,
+    nodes: Vec<N>,
+    /// (best cost so far, index of the node it came from)
+    best: Vec<(C, usize)>,
+}
+
+impl<N: Eq + Hash + Clone, C: Copy> Visited<N, C> {
+    fn new(start: N, zero: C) -> Self {
+        Visited {
+            index: HashMap::from([(start.clone(), 0)]),
+            nodes: vec![start],
+            best: vec![(zero, 0)],
+        }
+    }
+
+    /// Record reaching `node` from `parent` for `cost` if that's the best yet,
+    /// returning its index if so.
+    fn relax(
+        &mut self,
+        node: N,
+        parent: usize,
+        cost: C,
+        better: impl Fn(C, C) -> bool,
+    ) -> Option<usize> {
+        match self.index.entry(node) {
+            Entry::Occupied(e) => {
+                let i = *e.get();
+                if !better(cost, self.best[i].0) {
+                    return None;
+                }
+                self.best[i] = (cost, parent);
+                Some(i)
+            }
+            Entry::Vacant(e) => {
+                let i = self.nodes.len();
+                self.nodes.push(e.key().clone());
+                e.insert(i);
+                self.best.push((cost, parent));
+                Some(i)
+            }
+        }
+    }
+
+    fn route(&self, mut i: usize) -> Route<N, C> {
+        let cost = self.best[i].0;
+        let mut path = vec![self.nodes[i].clone()];
+        while i != 0 {
+            i = self.best[i].1;
+            path.push(self.nodes[i].clone());
+        }
+        path.reverse();
+        Route { cost, path }
+    }
+}
+
+/// Breadth-first search for the fewest steps from `start` to a node
+/// satisfying `goal`.
+pub fn bfs<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Route<N, usize>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = Visited::new(start, 0);
+    let mut queue = VecDeque::from([0]);
+    while let Some(i) = queue.pop_front() {
+        if goal(&visited.nodes[i]) {
+            return Some(visited.route(i));
+        }
+        let steps = visited.best[i].0 + 1;
+        for next in successors(&visited.nodes[i]) {
+            // the first visit is always the shortest
+            if let Some(n) = visited.relax(next, i, steps, |_, _| false) {
+                queue.push_back(n);
+            }
+        }
+    }
+    None
+}
+
+/// Dijkstra's algorithm: the cheapest route from `start` to a node
+/// satisfying `goal`, where `successors` gives each neighbor and the cost
+/// of stepping to it.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Route<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, goal, |_| C::zero())
+}
+
+/// A* search: like [`dijkstra`], but guided by a `heuristic` estimate of the
+/// remaining cost to a goal.
+///
+/// The heuristic must never overestimate, or the route found may not be the
+/// cheapest.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+    mut heuristic: impl FnMut(&N) -> C,
+) -> Option<Route<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::zero(), 0))]);
+    let mut visited = Visited::new(start, C::zero());
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        if cost > visited.best[i].0 {
+            // a cheaper way here was already handled
+            continue;
+        }
+        if goal(&visited.nodes[i]) {
+            return Some(visited.route(i));
+        }
+        for (next, step) in successors(&visited.nodes[i]) {
+            let next_cost = cost + step;
+            let h = heuristic(&next);
+            if let Some(n) = visited.relax(next, i, next_cost, |new, old| new < old) {
+                heap.push(Reverse((next_cost + h, next_cost, n)));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Connectivity, Edges, Grid};
+
+    const CAVE: &str = "\
+1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+    #[test]
+    fn test_grid() {
+        let cave = Grid::parse(CAVE, |c| c.to_digit(10)).unwrap();
+        let end = (cave.nrows() - 1, cave.ncols() - 1);
+        let successors = |&pos: &(usize, usize)| {
+            cave.neighbors(pos, Connectivity::Orthogonal, Edges::Bounded)
+                .map(|n| (n, cave[n]))
+        };
+        let route = dijkstra((0, 0), successors, |&pos| pos == end).unwrap();
+        assert_eq!(route.cost, 40);
+        assert_eq!(route.path.first(), Some(&(0, 0)));
+        assert_eq!(route.path.last(), Some(&end));
+        let manhattan = |&(r, c): &(usize, usize)| (end.0 - r + end.1 - c) as u32;
+        let a = astar((0, 0), successors, |&pos| pos == end, manhattan).unwrap();
+        assert_eq!(a.cost, 40);
+
+        let steps = bfs(
+            (0, 0),
+            |&pos| cave.neighbors(pos, Connectivity::Orthogonal, Edges::Bounded),
+            |&pos| pos == end,
+        )
+        .unwrap();
+        assert_eq!(steps.cost, 18);
+        assert_eq!(steps.path.len(), 19);
+        assert_eq!(
+            bfs(0, |&x| [x + 1].into_iter().filter(|&x| x < 5), |&x| x == 9),
+            None
+        );
+    }
+}
@@ -1,17 +1,40 @@
 use common::get_input;
-use itertools::Itertools;
 
-fn part1(iter: impl Iterator<Item = u32>) -> usize {
-    iter.tuple_windows().filter(|(x, y)| x < y).count()
+/// Counts how often a value is bigger than the one before it
+#[derive(Default)]
+struct Increases {
+    last: Option<u32>,
+    count: usize,
 }
 
-fn part2(iter: impl Iterator<Item = u32>) -> usize {
-    part1(iter.tuple_windows().map(|(x, y, z)| x + y + z))
+impl Increases {
+    fn push(&mut self, x: u32) {
+        if self.last.is_some_and(|last| last < x) {
+            self.count += 1;
+        }
+        self.last = Some(x);
+    }
+}
+
+/// Both parts in one pass, since a streamed input can only be read once
+fn solve(iter: impl Iterator<Item = u32>) -> (usize, usize) {
+    let mut singles = Increases::default();
+    let mut sums = Increases::default();
+    let mut window = [0; 3];
+    for (i, x) in iter.enumerate() {
+        singles.push(x);
+        window = [window[1], window[2], x];
+        if i >= 2 {
+            sums.push(window.iter().sum());
+        }
+    }
+    (singles.count, sums.count)
 }
 
 fn main() {
-    println!("Part 1: {}", part1(get_input!(parsed)));
-    println!("Part 2: {}", part2(get_input!(parsed)));
+    let (part1, part2) = solve(get_input!(stream parsed));
+    println!("Part 1: {}", part1);
+    println!("Part 2: {}", part2);
 }
 
 #[cfg(test)]
@@ -22,11 +45,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(DATA.into_iter()), 7);
+        assert_eq!(solve(DATA.into_iter()).0, 7);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(DATA.into_iter()), 5);
+        assert_eq!(solve(DATA.into_iter()).1, 5);
     }
 }
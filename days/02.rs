@@ -1,25 +1,13 @@
 use std::str::FromStr;
 
-use common::{get_input, PanicOnError};
+use common::{get_input, parse_line, Answer, PanicOnError};
 
-fn part1(iter: impl Iterator<Item = Movement>) -> i32 {
-    let mut pos = 0;
-    let mut depth = 0;
-    use Movement::*;
-    for movement in iter {
-        match movement {
-            Forward(a) => pos += a,
-            Down(a) => depth += a,
-            Up(a) => depth -= a,
-        }
-    }
-    pos * depth
-}
-
-fn part2(iter: impl Iterator<Item = Movement>) -> i32 {
-    let mut pos = 0;
-    let mut depth = 0;
-    let mut aim = 0;
+/// Both parts in one pass, since a streamed input can only be read once.
+/// Part 1's depth is the same as part 2's aim.
+fn solve(iter: impl Iterator<Item = Movement>) -> (Answer<i32>, Answer<i32>) {
+    let mut pos = Answer(0);
+    let mut depth = Answer(0);
+    let mut aim = Answer(0);
     use Movement::*;
     for movement in iter {
         match movement {
@@ -31,7 +19,7 @@ fn part2(iter: impl Iterator<Item = Movement>) -> i32 {
             Up(a) => aim -= a,
         }
     }
-    pos * depth
+    (pos * aim, pos * depth)
 }
 
 enum Movement {
@@ -44,10 +32,8 @@ impl FromStr for Movement {
     type Err = PanicOnError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut i = s.split(" ");
-        let direction = i.next().unwrap();
-        let amount: i32 = i.next().unwrap().parse()?;
-        Ok(match direction {
+        let (direction, amount) = parse_line!(s, "{} {}", String, i32)?;
+        Ok(match direction.as_str() {
             "forward" => Movement::Forward(amount),
             "down" => Movement::Down(amount),
             "up" => Movement::Up(amount),
@@ -57,6 +43,7 @@ impl FromStr for Movement {
 }
 
 fn main() {
-    println!("Part 1: {}", part1(get_input!(parsed)));
-    println!("Part 2: {}", part2(get_input!(parsed)));
+    let (part1, part2) = solve(get_input!(stream parsed));
+    println!("Part 1: {}", part1);
+    println!("Part 2: {}", part2);
 }
@@ -1,7 +1,7 @@
 // This almost certainly would've been easier with string manipulation
 // instead of the bit twiddling I did.
 
-use common::get_input;
+use common::{get_input, Answer, BitSet};
 
 fn read_bins<'a>(iter: impl Iterator<Item = &'a str>) -> Vec<u32> {
     iter.filter(|x| !x.trim().is_empty())
@@ -10,22 +10,17 @@ fn read_bins<'a>(iter: impl Iterator<Item = &'a str>) -> Vec<u32> {
 }
 
 // todo: refactor part1 to use part2 fn's?
-fn part1(iter: impl Iterator<Item = u32>) -> u64 {
+fn part1(iter: impl Iterator<Item = u32>) -> Answer<u64> {
     let values: Vec<u32> = iter.collect();
     let len = values.len() as u32;
     // counts of set bits
 
     let mut counts = [0u32; 32];
     let mut max_width = 0;
-    for mut value in values {
-        while value > 0 {
-            // let nonzero = NonZeroU32::new(value).unwrap();
-            // lowest set bit
-            let bit = value.trailing_zeros();
-            counts[bit as usize] += 1;
-            // clear that bit
-            value &= value - 1;
-            max_width = bit.max(max_width);
+    for value in values {
+        for bit in BitSet::<32>::from(value as u64).iter() {
+            counts[bit] += 1;
+            max_width = (bit as u32).max(max_width);
         }
     }
 
@@ -43,7 +38,7 @@ fn part1(iter: impl Iterator<Item = u32>) -> u64 {
         }
     }
     assert_eq!(epsilon, (!gamma) & ((1 << (max_width + 1)) - 1));
-    gamma * epsilon
+    Answer(gamma) * epsilon
 }
 
 fn max_width(iter: impl Iterator<Item = u32>) -> Option<u32> {
@@ -63,7 +58,7 @@ fn bit_is_mostly_set(iter: impl Iterator<Item = u32>, bit: u32) -> bool {
 
 fn find_rating(mut values: Vec<u32>, max_width: u32, flip: bool) -> Option<u32> {
     let mut mask = 0;
-    for bit in (0..max_width).into_iter().rev() {
+    for bit in (0..max_width).rev() {
         let set = bit_is_mostly_set(values.iter().copied(), bit) ^ flip;
         mask |= (set as u32) << bit;
         values.retain(|&x| (x >> bit) << bit == mask);
@@ -74,12 +69,12 @@ fn find_rating(mut values: Vec<u32>, max_width: u32, flip: bool) -> Option<u32>
     None
 }
 
-fn part2(iter: impl Iterator<Item = u32>) -> u64 {
+fn part2(iter: impl Iterator<Item = u32>) -> Answer<u64> {
     let values: Vec<u32> = iter.collect();
     let max_width = max_width(values.iter().copied()).unwrap();
     let oxygen = find_rating(values.clone(), max_width, false).unwrap() as u64;
     let co2 = find_rating(values.clone(), max_width, true).unwrap() as u64;
-    oxygen * co2
+    Answer(oxygen) * co2
 }
 
 fn main() {
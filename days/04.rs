@@ -1,9 +1,9 @@
 // This almost certainly would've been easier with string manipulation
 // instead of the bit twiddling I did.
 
-use std::{collections::HashMap, iter};
+use std::collections::HashMap;
 
-use common::get_input;
+use common::{estimate_if_requested, gcd, get_input, Answer, ArrayCollect};
 use ndarray::{Array, Array2, ArrayView, ArrayView2, ArrayViewMut2, Zip};
 
 #[derive(Clone, Debug)]
@@ -30,14 +30,13 @@ fn parse_input<'a>(mut iter: impl Iterator<Item = &'a str>) -> Input {
 
         assert!(empty.is_empty());
         for _ in 0..5 {
-            let row = iter
+            let row: [i32; 5] = iter
                 .next()
                 .expect("missing bingo row")
                 .split_ascii_whitespace()
-                .map(str::parse)
-                .collect::<Result<Vec<i32>, _>>()
-                .expect("broken bingo");
-            assert_eq!(row.len(), 5);
+                .map(|x| x.parse().expect("broken bingo"))
+                .try_array_collect()
+                .expect("bingo rows have 5 numbers");
             board.push_row(ArrayView::from(&row)).unwrap();
         }
         // assert_eq!(rows.len(), 5);
@@ -53,14 +52,19 @@ fn mark_board(mut a: ArrayViewMut2<bool>, (row, col): (usize, usize)) -> bool {
 }
 
 /// Returns the sum of all unmarked pieces
-fn unmarked_score(pieces: ArrayView2<i32>, marks: ArrayView2<bool>) -> i32 {
+fn unmarked_score(pieces: ArrayView2<i32>, marks: ArrayView2<bool>) -> Answer<i32> {
     Zip::from(&pieces)
         .and(&marks)
-        .fold(0, |acc, &piece, &marked| acc + piece * (!marked as i32))
+        .fold(Answer(0), |acc, &piece, &marked| {
+            acc + Answer(piece) * (!marked as i32)
+        })
 }
 
-fn calc_positions(boards: &[Board]) -> HashMap<i32, Vec<(usize, (usize, usize))>> {
-    let mut positions: HashMap<i32, Vec<(usize, (usize, usize))>> = HashMap::new();
+/// (board index, (row, col)) of every place a ball appears
+type Positions = HashMap<i32, Vec<(usize, (usize, usize))>>;
+
+fn calc_positions(boards: &[Board]) -> Positions {
+    let mut positions: Positions = HashMap::new();
     for (idx, board) in boards.iter().enumerate() {
         for (pos, &ball) in board.0.indexed_iter() {
             positions.entry(ball).or_default().push((idx, pos));
@@ -69,7 +73,23 @@ fn calc_positions(boards: &[Board]) -> HashMap<i32, Vec<(usize, (usize, usize))>
     positions
 }
 
-fn part1(input: &Input) -> i32 {
+/// A board that just got bingo, and its marks at that point
+struct Bingo {
+    idx: usize,
+    marks: Array2<bool>,
+    drawing: i32,
+}
+
+impl Bingo {
+    fn score(&self, input: &Input) -> Answer<i32> {
+        unmarked_score(
+            ArrayView::from(&input.boards[self.idx].0),
+            ArrayView::from(&self.marks),
+        ) * self.drawing
+    }
+}
+
+fn first_bingo(input: &Input) -> Bingo {
     let mut marks: Vec<Array2<bool>> = input
         .boards
         .iter()
@@ -80,13 +100,13 @@ fn part1(input: &Input) -> i32 {
     // make marks on board
     for drawing in &input.drawings {
         if let Some(p) = positions.get(drawing) {
-            for (idx, pos) in p {
-                if mark_board(ArrayViewMut2::from(&mut marks[*idx]), *pos) {
-                    println!("bingo!\n{:?}", marks[*idx]);
-                    return unmarked_score(
-                        ArrayView::from(&input.boards[*idx].0),
-                        ArrayView::from(&marks[*idx]),
-                    ) * *drawing;
+            for &(idx, pos) in p {
+                if mark_board(ArrayViewMut2::from(&mut marks[idx]), pos) {
+                    return Bingo {
+                        idx,
+                        marks: marks.swap_remove(idx),
+                        drawing: *drawing,
+                    };
                 }
             }
         }
@@ -94,37 +114,32 @@ fn part1(input: &Input) -> i32 {
     panic!("no win after drawings!")
 }
 
-fn part2(input: &Input) -> i32 {
+fn last_bingo(input: &Input) -> Bingo {
     let mut marks: Vec<Array2<bool>> = input
         .boards
         .iter()
         .map(|_| Array::default((5, 5)))
         .collect();
     let positions = calc_positions(&input.boards);
-    let mut got_bingo: Vec<bool> = iter::repeat(false).take(marks.len()).collect();
+    let mut got_bingo: Vec<bool> = vec![false; marks.len()];
     let mut num_bingos = 0;
 
     // there are definitely more algorithmically kind ways to do this
     // bounded input is bounded input
     for drawing in &input.drawings {
         if let Some(p) = positions.get(drawing) {
-            for (idx, pos) in p {
-                let idx = *idx;
-                let pos = *pos;
+            for &(idx, pos) in p {
                 if got_bingo[idx] {
                     continue;
                 }
                 if mark_board(ArrayViewMut2::from(&mut marks[idx]), pos) {
                     num_bingos += 1;
                     if num_bingos == input.boards.len() {
-                        println!(
-                            "last bingo (win {}, idx {})!\n{:?}\n{:?}",
-                            num_bingos, idx, input.boards[idx].0, marks[idx]
-                        );
-                        return unmarked_score(
-                            ArrayView::from(&input.boards[idx].0),
-                            ArrayView::from(&marks[idx]),
-                        ) * *drawing;
+                        return Bingo {
+                            idx,
+                            marks: marks.swap_remove(idx),
+                            drawing: *drawing,
+                        };
                     }
                     got_bingo[idx] = true;
                 }
@@ -134,8 +149,61 @@ fn part2(input: &Input) -> i32 {
     panic!("no last bingo?");
 }
 
+fn part1(input: &Input) -> Answer<i32> {
+    first_bingo(input).score(input)
+}
+
+fn part2(input: &Input) -> Answer<i32> {
+    last_bingo(input).score(input)
+}
+
+/// `n` boards of 25 distinct balls out of 100, all drawn in a scrambled
+/// order, for `AOC_COMPLEXITY`. The balls don't grow with `n`, so the scores
+/// stay in range.
+fn generate(n: usize) -> Input {
+    // 37 is coprime to 100, so this visits every ball once
+    let drawings = (0..100).map(|i| i * 37 % 100).collect();
+    let steps: Vec<i32> = (1..100).filter(|&s| gcd(s, 100) == 1).collect();
+    let boards = (0..n)
+        .map(|b| {
+            // with a step coprime to 100, 25 steps never come back around
+            let (start, step) = ((b * 31 % 100) as i32, steps[b % steps.len()]);
+            Board(Array::from_shape_fn((5, 5), |(r, c)| {
+                (start + (r * 5 + c) as i32 * step) % 100
+            }))
+        })
+        .collect();
+    Input { drawings, boards }
+}
+
 fn main() {
+    estimate_if_requested(generate, &[("Part 1", part1), ("Part 2", part2)]);
     let input = parse_input(get_input!(lines));
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    let first = first_bingo(&input);
+    println!("bingo!\n{:?}", first.marks);
+    println!("Part 1: {}", first.score(&input));
+    let last = last_bingo(&input);
+    println!(
+        "last bingo (win {}, idx {})!\n{:?}\n{:?}",
+        input.boards.len(),
+        last.idx,
+        input.boards[last.idx].0,
+        last.marks
+    );
+    println!("Part 2: {}", last.score(&input));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::DEFAULT_SIZES;
+
+    #[test]
+    fn test_generate() {
+        for n in DEFAULT_SIZES {
+            let input = generate(n);
+            part1(&input);
+            part2(&input);
+        }
+    }
 }
@@ -1,32 +1,19 @@
 use std::{cmp::Ordering, ops::RangeInclusive, str::FromStr};
 
-use common::{get_input, Dots, PanicOnError};
+use common::{
+    get_input, parse_line, save_image_if_requested, Color, PanicOnError, Point2, Renderer,
+};
 use ndarray::{s, Array2};
 
-#[derive(Clone, Debug)]
-struct Position {
-    x: i32,
-    y: i32,
-}
-impl FromStr for Position {
-    type Err = PanicOnError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, y) = s.split_once(",").unwrap();
-        let (x, y) = (x.parse()?, y.parse()?);
-        Ok(Position { x, y })
-    }
-}
-
 #[derive(Clone, Debug)]
 struct Line {
-    from: Position,
-    to: Position,
+    from: Point2<i32>,
+    to: Point2<i32>,
 }
 enum LineOrientation {
     Horizontal,
     Vertical,
-    Diagonal { rising: bool },
+    Diagonal,
 }
 
 impl Line {
@@ -49,14 +36,9 @@ impl Line {
         } else if self.from.y == self.to.y {
             LineOrientation::Horizontal
         } else {
-            assert!(
-                (self.from.x - self.to.x).abs() == (self.from.y - self.to.y).abs(),
-                "line {:?} not 45 degrees",
-                self
-            );
-            LineOrientation::Diagonal {
-                rising: self.from.y > self.to.y,
-            }
+            let d = self.to - self.from;
+            assert!(d.x.abs() == d.y.abs(), "line {:?} not 45 degrees", self);
+            LineOrientation::Diagonal
         }
     }
 }
@@ -64,8 +46,7 @@ impl FromStr for Line {
     type Err = PanicOnError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (from, to) = s.split_once(" -> ").unwrap();
-        let (from, to) = (from.parse()?, to.parse()?);
+        let (from, to) = parse_line!(s, "{} -> {}", Point2<i32>, Point2<i32>)?;
         Ok(Line { from, to }.normalize())
     }
 }
@@ -100,7 +81,7 @@ fn part1(input: &Input) -> usize {
                 let mut line_content = col.slice_mut(s![line_range(line.from.y, line.to.y)]);
                 line_content += 1;
             }
-            LineOrientation::Diagonal { .. } => (),
+            LineOrientation::Diagonal => (),
         }
     }
     map.into_iter().filter(|&x| x > 1).count()
@@ -120,16 +101,23 @@ fn part2(input: &Input) -> usize {
                 let mut line_content = col.slice_mut(s![line_range(line.from.y, line.to.y)]);
                 line_content += 1;
             }
-            LineOrientation::Diagonal { rising } => {
-                let mut y = line.from.y;
-                for x in line.from.x..=line.to.x {
-                    map[(y as usize, x as usize)] += 1;
-                    y += if rising { -1 } else { 1 };
+            LineOrientation::Diagonal => {
+                let step = (line.to - line.from).signum();
+                let mut pos = line.from;
+                for _ in line.from.x..=line.to.x {
+                    map[pos.to_index().expect("negative position")] += 1;
+                    pos += step;
                 }
             }
         }
     }
-    println!("map:\n{}", Dots(&map));
+    let overlaps = |&x: &i32| match x {
+        0 => '.',
+        1..=9 => char::from_digit(x as u32, 10).unwrap(),
+        _ => '+',
+    };
+    println!("map:\n{}", Renderer::new(&map, overlaps).axes());
+    save_image_if_requested(&map, |&x| Color::heat(x as f64 / 4.));
     map.into_iter().filter(|&x| x > 1).count()
 }
 
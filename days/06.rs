@@ -1,24 +1,24 @@
 use std::{collections::VecDeque, str::FromStr};
 
-use common::{get_input, PanicOnError};
+use common::{debug_if_requested, get_input, ints, Answer, Matrix, PanicOnError, Simulation};
 
 struct Input {
-    counts: [i64; 9],
+    counts: [Answer<i64>; 9],
 }
 
 impl FromStr for Input {
     type Err = PanicOnError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut counts = [0; 9];
-        for i in s.split(',') {
-            counts[i.parse::<usize>()?] += 1;
+        let mut counts = [Answer(0); 9];
+        for i in ints::<usize>(s) {
+            counts[i] += 1;
         }
         Ok(Input { counts })
     }
 }
 
-fn run_sim(state: &mut VecDeque<i64>, days: usize) {
+fn run_sim(state: &mut VecDeque<Answer<i64>>, days: usize) {
     assert_eq!(state.len(), 9);
     for _ in 0..days {
         let atzero = state.pop_front().expect("empty state");
@@ -27,22 +27,53 @@ fn run_sim(state: &mut VecDeque<i64>, days: usize) {
     }
 }
 
-fn part1(input: &Input) -> i64 {
-    let mut state: VecDeque<i64> = input.counts.iter().copied().collect();
-    state.resize(9, 0);
-    run_sim(&mut state, 80);
-    state.into_iter().sum()
+/// Skip ahead `days` at once: each day is the same linear map of the
+/// buckets, so raise its matrix to the `days`.
+fn jump_sim(counts: [Answer<i64>; 9], days: u64) -> [Answer<i64>; 9] {
+    let mut day = Matrix::zero();
+    for timer in 0..8 {
+        day.0[timer][timer + 1] = Answer(1);
+    }
+    // fish at zero reset to 6 and spawn a new fish at 8
+    day.0[6][0] = Answer(1);
+    day.0[8][0] = Answer(1);
+    day.pow(days) * counts
+}
+
+/// The fish buckets, for stepping through with `AOC_DEBUG=1`
+struct School(VecDeque<Answer<i64>>);
+
+impl Simulation for School {
+    fn step(&mut self) {
+        run_sim(&mut self.0, 1);
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        for (timer, count) in self.0.iter().enumerate() {
+            out += &format!("{}: {}\n", timer, count);
+        }
+        out
+    }
+
+    fn summary(&self) -> Vec<(&'static str, i64)> {
+        let fish = Answer::sum_checked(self.0.iter().copied());
+        vec![("fish", fish.0), ("spawning", self.0[0].0)]
+    }
+}
+
+fn part1(input: &Input) -> Answer<i64> {
+    Answer::sum_checked(jump_sim(input.counts, 80))
 }
 
-fn part2(input: &Input) -> i64 {
-    let mut state: VecDeque<i64> = input.counts.iter().copied().collect();
-    state.resize(9, 0);
-    run_sim(&mut state, 256);
-    state.into_iter().sum()
+fn part2(input: &Input) -> Answer<i64> {
+    Answer::sum_checked(jump_sim(input.counts, 256))
 }
 
 fn main() {
-    let input = get_input!().parse().unwrap();
+    let input: Input = get_input!().parse().unwrap();
+    let mut school = School(input.counts.iter().copied().collect());
+    debug_if_requested(&mut school);
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
 }
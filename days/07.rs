@@ -1,46 +1,43 @@
-use std::{collections::BTreeMap, str::FromStr};
+use std::str::FromStr;
 
-use common::{get_input, PanicOnError};
+use common::{estimate_if_requested, get_input, ints, Answer, Counter, PanicOnError};
 use itertools::Itertools;
 
 struct Input {
-    // BTreeMap because originally I cared about key order.
-    // Still real fast to iterate though.
-    counts: BTreeMap<i32, i32>,
+    counts: Counter<i32>,
 }
 
 impl FromStr for Input {
     type Err = PanicOnError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // is there a good iterator-y way to do this?
-        let mut counts = BTreeMap::new();
-        for i in s.trim_end().split(',') {
-            *counts.entry(i.parse()?).or_default() += 1;
-        }
-        Ok(Input { counts })
+        Ok(Input {
+            counts: ints(s).into_iter().collect(),
+        })
     }
 }
 
-fn calc_cost_to(items: &BTreeMap<i32, i32>, dest: i32) -> i32 {
-    items
-        .iter()
-        .map(|(pos, count)| (pos - dest).abs() * count)
-        .sum()
+fn crabs(count: usize) -> i32 {
+    i32::try_from(count).expect("too many crabs in one place")
 }
 
-fn calc_pricey_cost_to(items: &BTreeMap<i32, i32>, dest: i32) -> i32 {
-    items
-        .iter()
-        .map(|(pos, count)| {
-            let distance = (pos - dest).abs();
-            // Sum of the series [1, n]: n(n+1)/2
-            (distance + 1) * distance / 2 * count
-        })
-        .sum()
+fn calc_cost_to(items: &Counter<i32>, dest: i32) -> Answer<i32> {
+    Answer::sum_checked(
+        items
+            .iter()
+            .map(|(pos, count)| Answer((pos - dest).abs()) * crabs(count)),
+    )
 }
 
-fn part1(input: &Input) -> i32 {
+fn calc_pricey_cost_to(items: &Counter<i32>, dest: i32) -> Answer<i32> {
+    Answer::sum_checked(items.iter().map(|(pos, count)| {
+        let distance = Answer((pos - dest).abs());
+        // Sum of the series [1, n]: n(n+1)/2
+        (distance + 1) * distance / 2 * crabs(count)
+    }))
+}
+
+fn part1(input: &Input) -> Answer<i32> {
     // there are more efficient ways to do this for sure
     let (min, max) = input.counts.keys().minmax().into_option().unwrap();
     (*min..=*max)
@@ -49,7 +46,7 @@ fn part1(input: &Input) -> i32 {
         .unwrap()
 }
 
-fn part2(input: &Input) -> i32 {
+fn part2(input: &Input) -> Answer<i32> {
     let (min, max) = input.counts.keys().minmax().into_option().unwrap();
     (*min..=*max)
         .map(|pos| calc_pricey_cost_to(&input.counts, pos))
@@ -57,8 +54,32 @@ fn part2(input: &Input) -> i32 {
         .unwrap()
 }
 
+/// `n` crabs spread over positions `0..500`, for `AOC_COMPLEXITY`. The
+/// positions don't grow with `n`, so the fuel costs stay in range.
+fn generate(n: usize) -> Input {
+    Input {
+        counts: (0..n).map(|i| (i * 7919 % 500) as i32).collect(),
+    }
+}
+
 fn main() {
+    estimate_if_requested(generate, &[("Part 1", part1), ("Part 2", part2)]);
     let input = get_input!().parse().unwrap();
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::DEFAULT_SIZES;
+
+    #[test]
+    fn test_generate() {
+        for n in DEFAULT_SIZES {
+            let input = generate(n);
+            part1(&input);
+            part2(&input);
+        }
+    }
+}
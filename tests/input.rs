@@ -0,0 +1,43 @@
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+fn run_day_01(arg: &str, stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_01"))
+        .arg(arg)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("could not run day 1");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn test_stdin_input_reaches_both_parts() {
+    let output = run_day_01("-", "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Part 1: 7\nPart 2: 5\n"
+    );
+}
+
+#[test]
+fn test_missing_input_without_terminal() {
+    let output = run_day_01("no/such/input.txt", "1\n2\n");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("no/such/input.txt not found and stdin is not a terminal"),
+        "{}",
+        stderr
+    );
+}